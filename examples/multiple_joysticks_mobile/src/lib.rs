@@ -67,6 +67,7 @@ fn create_scene(mut cmd: Commands, asset_server: Res<AssetServer>) {
             id: JoystickController::MovementX,
             axis: VirtualJoystickAxis::Horizontal,
             behaviour: VirtualJoystickType::Fixed,
+            ..default()
         },
         Style {
             width: Val::Px(150.),
@@ -93,6 +94,7 @@ fn create_scene(mut cmd: Commands, asset_server: Res<AssetServer>) {
             id: JoystickController::MovementY,
             axis: VirtualJoystickAxis::Vertical,
             behaviour: VirtualJoystickType::Fixed,
+            ..default()
         },
         Style {
             width: Val::Px(150.),
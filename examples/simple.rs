@@ -53,6 +53,7 @@ fn create_scene(mut cmd: Commands, asset_server: Res<AssetServer>) {
             id: "UniqueJoystick".to_string(),
             axis: VirtualJoystickAxis::Both,
             behaviour: VirtualJoystickType::Floating,
+            ..default()
         },
         Style {
             width: Val::Px(150.),
@@ -0,0 +1,78 @@
+use bevy::{
+    ecs::system::SystemParam,
+    prelude::*,
+    ui::{FocusPolicy, UiStack},
+};
+
+use crate::{VirtualJoystickCapturedPointers, VirtualJoystickPointer};
+
+/// Ui nodes with [`Interaction`] like buttons, they claim pointers
+/// pressed on them before joysticks stacked below
+#[allow(clippy::type_complexity)]
+#[derive(SystemParam)]
+pub struct UiPointerBlockers<'w, 's> {
+    ui_stack: Option<Res<'w, UiStack>>,
+    nodes: Query<
+        'w,
+        's,
+        (
+            &'static Node,
+            &'static GlobalTransform,
+            &'static ViewVisibility,
+            Option<&'static FocusPolicy>,
+        ),
+        With<Interaction>,
+    >,
+}
+
+impl<'w, 's> UiPointerBlockers<'w, 's> {
+    /// Position of a node in ui stacking order, higher is on top
+    pub(crate) fn stack_index(&self, entity: Entity) -> Option<usize> {
+        self.ui_stack
+            .as_ref()?
+            .uinodes
+            .iter()
+            .position(|e| *e == entity)
+    }
+
+    /// A visible interactive node stacked above `joystick` contains `pos`,
+    /// without joystick every node is above, like for world joysticks
+    pub(crate) fn is_blocked(&self, joystick: Option<Entity>, pos: Vec2) -> bool {
+        let Some(ui_stack) = self.ui_stack.as_ref() else {
+            return false;
+        };
+        let above = match joystick {
+            Some(joystick) => match self.stack_index(joystick) {
+                Some(index) => index + 1,
+                None => return false,
+            },
+            None => 0,
+        };
+        ui_stack.uinodes[above..].iter().any(|entity| {
+            let Ok((node, transform, visibility, focus_policy)) = self.nodes.get(*entity) else {
+                return false;
+            };
+            visibility.get()
+                && focus_policy != Some(&FocusPolicy::Pass)
+                && node.logical_rect(transform).contains(pos)
+        })
+    }
+}
+
+/// Ui [`Interaction`] follows the mouse or first touch, it is reset while
+/// that pointer drags a joystick so nodes under the drag do not react
+pub(crate) fn block_captured_interaction(
+    captured: Res<VirtualJoystickCapturedPointers>,
+    touches: Option<Res<Touches>>,
+    mut interactions: Query<&mut Interaction, Changed<Interaction>>,
+) {
+    let first_touch = touches.as_ref().and_then(|touches| touches.iter().next());
+    let focus_captured = captured.is_mouse_captured()
+        || matches!(first_touch, Some(touch) if captured.is_captured(touch.id(), VirtualJoystickPointer::Touch));
+    if !focus_captured {
+        return;
+    }
+    for mut interaction in &mut interactions {
+        interaction.set_if_neq(Interaction::None);
+    }
+}
@@ -0,0 +1,57 @@
+use bevy::prelude::*;
+
+use crate::{VirtualJoystickID, VirtualJoystickNode};
+
+/// Joystick stays visible but ignores pointers, adding it while dragged
+/// releases the knob and sends [`crate::VirtualJoystickEventType::Up`].
+/// Tinted with [`crate::VirtualJoystickVisuals::disabled_tint`]
+///
+/// Usage:
+/// ```ignore
+/// cmd.entity(joystick).insert(VirtualJoystickDisabled);
+/// // later
+/// cmd.entity(joystick).remove::<VirtualJoystickDisabled>();
+/// ```
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickDisabled;
+
+/// Disable every joystick of ID type `S`, pair it with [`enable_joysticks`]
+/// to turn joysticks off in some states. Already disabled joysticks are skipped,
+/// so running it every frame under a run condition also disables joysticks
+/// spawned while the condition holds, unlike running it once in `OnEnter`
+///
+/// Usage:
+/// ```ignore
+/// app.add_systems(
+///     PreUpdate,
+///     disable_joysticks::<String>
+///         .run_if(in_state(GameState::Cutscene))
+///         .before(VirtualJoystickSystem::ProcessJoysticks),
+/// )
+/// .add_systems(OnExit(GameState::Cutscene), enable_joysticks::<String>);
+/// ```
+pub fn disable_joysticks<S: VirtualJoystickID>(
+    mut cmd: Commands,
+    joysticks: Query<
+        Entity,
+        (
+            With<VirtualJoystickNode<S>>,
+            Without<VirtualJoystickDisabled>,
+        ),
+    >,
+) {
+    for entity in &joysticks {
+        cmd.entity(entity).insert(VirtualJoystickDisabled);
+    }
+}
+
+/// Enable every joystick of ID type `S`, see [`disable_joysticks`]
+pub fn enable_joysticks<S: VirtualJoystickID>(
+    mut cmd: Commands,
+    joysticks: Query<Entity, (With<VirtualJoystickNode<S>>, With<VirtualJoystickDisabled>)>,
+) {
+    for entity in &joysticks {
+        cmd.entity(entity).remove::<VirtualJoystickDisabled>();
+    }
+}
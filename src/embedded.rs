@@ -0,0 +1,78 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{asset::io::embedded::EmbeddedAssetRegistry, prelude::*};
+
+use crate::VirtualJoystickAxis;
+
+macro_rules! embed_image {
+    ($registry: ident, $name: literal) => {
+        $registry.insert_asset(
+            PathBuf::new(),
+            Path::new(concat!("virtual_joystick/", $name)),
+            include_bytes!(concat!("../assets/", $name)).as_slice(),
+        )
+    };
+}
+
+/// Register the crate images in the `embedded://` asset source,
+/// does nothing when [`AssetPlugin`] is not added
+pub(crate) fn embed_default_assets(app: &mut App) {
+    let Some(registry) = app.world.get_resource::<EmbeddedAssetRegistry>() else {
+        return;
+    };
+    embed_image!(registry, "Knob.png");
+    embed_image!(registry, "Knob_Arrows.png");
+    embed_image!(registry, "Horizontal_Knob.png");
+    embed_image!(registry, "Horizontal_Knob_Arrows.png");
+    embed_image!(registry, "Vertical_Knob.png");
+    embed_image!(registry, "Vertical_Knob_Arrows.png");
+    embed_image!(registry, "Outline.png");
+    embed_image!(registry, "Outline_Arrows.png");
+    embed_image!(registry, "Horizontal_Outline.png");
+    embed_image!(registry, "Horizontal_Outline_Arrows.png");
+    embed_image!(registry, "Vertical_Outline.png");
+    embed_image!(registry, "Vertical_Outline_Arrows.png");
+}
+
+/// Asset path of the embedded knob image for the axis
+///
+/// Usage:
+/// ```ignore
+/// let knob: Handle<Image> = asset_server.load(default_knob_image(VirtualJoystickAxis::Both, false));
+/// ```
+pub fn default_knob_image(axis: VirtualJoystickAxis, arrows: bool) -> &'static str {
+    match (axis, arrows) {
+        (VirtualJoystickAxis::Both, false) => "embedded://virtual_joystick/Knob.png",
+        (VirtualJoystickAxis::Both, true) => "embedded://virtual_joystick/Knob_Arrows.png",
+        (VirtualJoystickAxis::Horizontal, false) => {
+            "embedded://virtual_joystick/Horizontal_Knob.png"
+        }
+        (VirtualJoystickAxis::Horizontal, true) => {
+            "embedded://virtual_joystick/Horizontal_Knob_Arrows.png"
+        }
+        (VirtualJoystickAxis::Vertical, false) => "embedded://virtual_joystick/Vertical_Knob.png",
+        (VirtualJoystickAxis::Vertical, true) => {
+            "embedded://virtual_joystick/Vertical_Knob_Arrows.png"
+        }
+    }
+}
+
+/// Asset path of the embedded border image for the axis
+pub fn default_background_image(axis: VirtualJoystickAxis, arrows: bool) -> &'static str {
+    match (axis, arrows) {
+        (VirtualJoystickAxis::Both, false) => "embedded://virtual_joystick/Outline.png",
+        (VirtualJoystickAxis::Both, true) => "embedded://virtual_joystick/Outline_Arrows.png",
+        (VirtualJoystickAxis::Horizontal, false) => {
+            "embedded://virtual_joystick/Horizontal_Outline.png"
+        }
+        (VirtualJoystickAxis::Horizontal, true) => {
+            "embedded://virtual_joystick/Horizontal_Outline_Arrows.png"
+        }
+        (VirtualJoystickAxis::Vertical, false) => {
+            "embedded://virtual_joystick/Vertical_Outline.png"
+        }
+        (VirtualJoystickAxis::Vertical, true) => {
+            "embedded://virtual_joystick/Vertical_Outline_Arrows.png"
+        }
+    }
+}
@@ -0,0 +1,36 @@
+use bevy::prelude::*;
+
+/// Joystick state for systems running in `FixedUpdate`, presses and
+/// releases accumulate from frame updates until the end of the next
+/// fixed tick, so none is missed or seen twice
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickFixedInput {
+    /// Latest axis value, zero inside the dead zone (see [`crate::VirtualJoystickEvent::axis`])
+    pub axis: Vec2,
+    /// Joystick is being dragged
+    pub pressed: bool,
+    /// Presses since last fixed tick
+    pub presses: u32,
+    /// Releases since last fixed tick
+    pub releases: u32,
+}
+
+impl VirtualJoystickFixedInput {
+    /// Pressed at least once since last fixed tick
+    pub fn just_pressed(&self) -> bool {
+        self.presses > 0
+    }
+
+    /// Released at least once since last fixed tick
+    pub fn just_released(&self) -> bool {
+        self.releases > 0
+    }
+}
+
+pub(crate) fn reset_fixed_input(mut fixed_inputs: Query<&mut VirtualJoystickFixedInput>) {
+    for mut fixed in &mut fixed_inputs {
+        fixed.presses = 0;
+        fixed.releases = 0;
+    }
+}
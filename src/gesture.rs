@@ -0,0 +1,177 @@
+use std::collections::VecDeque;
+
+use bevy::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{VirtualJoystickAxis, VirtualJoystickEventType};
+
+/// Recognize taps, double taps, long presses and flicks on a joystick, sent as
+/// [`VirtualJoystickEventType::Tap`], [`VirtualJoystickEventType::DoubleTap`],
+/// [`VirtualJoystickEventType::LongPress`] and [`VirtualJoystickEventType::Flick`].
+/// Distances are in the space of the joystick, ui pixels or world units
+///
+/// Usage:
+/// ```ignore
+/// cmd.entity(joystick).insert(VirtualJoystickGestures::default());
+///
+/// fn dodge(mut events: EventReader<VirtualJoystickEvent<String>>) {
+///     for event in events.read() {
+///         if event.get_type() == VirtualJoystickEventType::DoubleTap {
+///             // dodge
+///         }
+///     }
+/// }
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickGestures {
+    /// Max seconds between press and release of a tap
+    pub tap_max_duration: f32,
+    /// Max distance moved during a tap
+    pub tap_max_distance: f32,
+    /// Max seconds between two taps of a double tap
+    pub double_tap_interval: f32,
+    /// Seconds held without moving before a long press
+    pub long_press_duration: f32,
+    /// Min speed of pointer at release for a flick, in distance per second
+    pub flick_min_speed: f32,
+    /// Seconds of pointer movement before release used to measure a flick
+    pub flick_sample_window: f32,
+    #[reflect(ignore)]
+    samples: VecDeque<(f32, Vec2)>,
+    #[reflect(ignore)]
+    press: Option<GesturePress>,
+    #[reflect(ignore)]
+    last_tap: Option<f32>,
+}
+
+/// Fast movement at release, see [`crate::VirtualJoystickEvent::flick`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VirtualJoystickFlick {
+    /// Normalized direction, x to the right and y up like joystick axis
+    pub direction: Vec2,
+    /// Distance per second
+    pub speed: f32,
+    /// Distance moved during the sample window
+    pub distance: f32,
+}
+
+#[derive(Clone, Debug)]
+struct GesturePress {
+    time: f32,
+    pos: Vec2,
+    moved: bool,
+    long_pressed: bool,
+}
+
+impl Default for VirtualJoystickGestures {
+    fn default() -> Self {
+        Self {
+            tap_max_duration: 0.25,
+            tap_max_distance: 10.,
+            double_tap_interval: 0.3,
+            long_press_duration: 0.5,
+            flick_min_speed: 800.,
+            flick_sample_window: 0.1,
+            samples: VecDeque::new(),
+            press: None,
+            last_tap: None,
+        }
+    }
+}
+
+impl VirtualJoystickGestures {
+    /// Keep pointer positions of the last sample window
+    fn sample(&mut self, time: f32, pos: Vec2) {
+        while matches!(self.samples.front(), Some((t, _)) if time - t > self.flick_sample_window) {
+            self.samples.pop_front();
+        }
+        self.samples.push_back((time, pos));
+    }
+
+    pub(crate) fn press(&mut self, time: f32, pos: Vec2) {
+        self.samples.clear();
+        self.sample(time, pos);
+        self.press = Some(GesturePress {
+            time,
+            pos,
+            moved: false,
+            long_pressed: false,
+        });
+    }
+
+    pub(crate) fn drag(&mut self, time: f32, pos: Vec2) {
+        self.sample(time, pos);
+        if let Some(press) = self.press.as_mut() {
+            press.moved |= press.pos.distance(pos) > self.tap_max_distance;
+        }
+    }
+
+    /// Interaction ended without gesture
+    pub(crate) fn cancel(&mut self) {
+        self.press = None;
+        self.samples.clear();
+    }
+
+    /// Movement along `axis` of pointer released at `pos` when fast enough
+    pub(crate) fn flick(
+        &mut self,
+        time: f32,
+        pos: Vec2,
+        axis: VirtualJoystickAxis,
+    ) -> Option<VirtualJoystickFlick> {
+        self.sample(time, pos);
+        let (start_time, start) = *self.samples.front()?;
+        let duration = time - start_time;
+        // Pointer positions are y down
+        let moved = axis.handle_xy(pos.x - start.x, start.y - pos.y);
+        let distance = moved.length();
+        if duration <= 0. || distance == 0. || distance / duration < self.flick_min_speed {
+            return None;
+        }
+        Some(VirtualJoystickFlick {
+            direction: moved / distance,
+            speed: distance / duration,
+            distance,
+        })
+    }
+
+    /// Gestures completed by a release at `pos`, a double tap is also a tap
+    pub(crate) fn release(&mut self, time: f32, pos: Vec2) -> Vec<VirtualJoystickEventType> {
+        self.samples.clear();
+        let Some(press) = self.press.take() else {
+            return Vec::new();
+        };
+        if press.moved
+            || press.pos.distance(pos) > self.tap_max_distance
+            || press.long_pressed
+            || time - press.time > self.tap_max_duration
+        {
+            return Vec::new();
+        }
+        if matches!(self.last_tap, Some(last) if time - last <= self.double_tap_interval) {
+            self.last_tap = None;
+            return vec![
+                VirtualJoystickEventType::Tap,
+                VirtualJoystickEventType::DoubleTap,
+            ];
+        }
+        self.last_tap = Some(time);
+        vec![VirtualJoystickEventType::Tap]
+    }
+
+    /// Held long enough without moving, true once per press
+    pub(crate) fn long_press(&mut self, time: f32) -> bool {
+        let Some(press) = self.press.as_mut() else {
+            return false;
+        };
+        if press.moved || press.long_pressed || time - press.time < self.long_press_duration {
+            return false;
+        }
+        press.long_pressed = true;
+        true
+    }
+}
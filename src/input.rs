@@ -1,20 +1,321 @@
 use bevy::{
-    input::{mouse::MouseButtonInput, ButtonState},
+    ecs::system::SystemParam,
+    input::{mouse::MouseButtonInput, touch::TouchPhase, ButtonState},
     prelude::*,
-    window::PrimaryWindow,
+    render::camera::NormalizedRenderTarget,
+    ui::UiScale,
+    utils::HashMap,
+    window::{PrimaryWindow, WindowFocused},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 use crate::VirtualJoystickID;
 use crate::{
-    ui::VirtualJoystickData, VirtualJoystickEvent, VirtualJoystickEventType, VirtualJoystickNode,
-    VirtualJoystickType,
+    disabled::VirtualJoystickDisabled,
+    fixed::VirtualJoystickFixedInput,
+    gesture::VirtualJoystickGestures,
+    lock::VirtualJoystickLock,
+    ownership::VirtualJoystickCapturedPointers,
+    pointer::VirtualJoystickPointer,
+    ui::VirtualJoystickData,
+    world::{world_pointer_position, VirtualJoystickWorldArea},
+    VirtualJoystickEvent, VirtualJoystickEventType, VirtualJoystickNode, VirtualJoystickType,
 };
 
-#[derive(Event)]
+/// Pointer input consumed by joysticks, sent by the plugin from touches and
+/// mouse or by hand in headless mode. `id` identifies the pointer and
+/// `pos` is in logical window coordinates, `window` is the window receiving
+/// the pointer, None matches joysticks on any window
+#[derive(Event, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub enum InputEvent {
-    StartDrag { id: u64, pos: Vec2, is_mouse: bool },
-    Dragging { id: u64, pos: Vec2, is_mouse: bool },
-    EndDrag { id: u64, pos: Vec2, is_mouse: bool },
+    StartDrag {
+        id: u64,
+        pos: Vec2,
+        pointer: VirtualJoystickPointer,
+        window: Option<Entity>,
+    },
+    Dragging {
+        id: u64,
+        pos: Vec2,
+        pointer: VirtualJoystickPointer,
+        window: Option<Entity>,
+    },
+    EndDrag {
+        id: u64,
+        pos: Vec2,
+        pointer: VirtualJoystickPointer,
+        window: Option<Entity>,
+    },
+    /// Pointer interrupted by the system instead of released by the user
+    CancelDrag {
+        id: u64,
+        pos: Vec2,
+        pointer: VirtualJoystickPointer,
+        window: Option<Entity>,
+    },
+}
+
+impl InputEvent {
+    fn window(&self) -> Option<Entity> {
+        match self {
+            InputEvent::StartDrag { window, .. }
+            | InputEvent::Dragging { window, .. }
+            | InputEvent::EndDrag { window, .. }
+            | InputEvent::CancelDrag { window, .. } => *window,
+        }
+    }
+
+    /// Id and kind identifying the pointer
+    pub(crate) fn pointer(&self) -> (u64, VirtualJoystickPointer) {
+        match self {
+            InputEvent::StartDrag { id, pointer, .. }
+            | InputEvent::Dragging { id, pointer, .. }
+            | InputEvent::EndDrag { id, pointer, .. }
+            | InputEvent::CancelDrag { id, pointer, .. } => (*id, *pointer),
+        }
+    }
+
+    /// Move of the same pointer to the position of this event
+    pub(crate) fn to_dragging(&self) -> Self {
+        match self {
+            InputEvent::StartDrag {
+                id,
+                pos,
+                pointer,
+                window,
+            }
+            | InputEvent::Dragging {
+                id,
+                pos,
+                pointer,
+                window,
+            }
+            | InputEvent::EndDrag {
+                id,
+                pos,
+                pointer,
+                window,
+            }
+            | InputEvent::CancelDrag {
+                id,
+                pos,
+                pointer,
+                window,
+            } => InputEvent::Dragging {
+                id: *id,
+                pos: *pos,
+                pointer: *pointer,
+                window: *window,
+            },
+        }
+    }
+
+    /// Same event matching joysticks on any window
+    pub(crate) fn without_window(&self) -> Self {
+        let mut event = self.clone();
+        match &mut event {
+            InputEvent::StartDrag { window, .. }
+            | InputEvent::Dragging { window, .. }
+            | InputEvent::EndDrag { window, .. }
+            | InputEvent::CancelDrag { window, .. } => *window = None,
+        }
+        event
+    }
+
+    /// Same event with position transformed, None if position can not be mapped,
+    /// an end or cancel of drag is never dropped so it keeps its original position instead.
+    /// `f` receives true for the start of a drag
+    fn map_pos(&self, f: impl FnOnce(Vec2, bool) -> Option<Vec2>) -> Option<Self> {
+        let mut event = self.clone();
+        match &mut event {
+            InputEvent::StartDrag { pos, .. } => {
+                *pos = f(*pos, true)?;
+            }
+            InputEvent::Dragging { pos, .. } => {
+                *pos = f(*pos, false)?;
+            }
+            InputEvent::EndDrag { pos, .. } | InputEvent::CancelDrag { pos, .. } => {
+                *pos = f(*pos, false).unwrap_or(*pos);
+            }
+        }
+        Some(event)
+    }
+}
+
+/// Converts pointer positions (logical window coordinates) to
+/// the space of each joystick
+#[derive(SystemParam)]
+pub struct PointerProjection<'w, 's> {
+    cameras: Query<'w, 's, (Entity, &'static Camera, &'static GlobalTransform)>,
+    primary_window: Query<'w, 's, Entity, With<PrimaryWindow>>,
+    ui_scale: Option<Res<'w, UiScale>>,
+}
+
+impl<'w, 's> PointerProjection<'w, 's> {
+    /// Position relative to the camera viewport, None when pointer is on another
+    /// window than the camera target or, if `inside`, out of the viewport
+    fn viewport_position(
+        &self,
+        pos: Vec2,
+        window: Option<Entity>,
+        camera: &Camera,
+        inside: bool,
+    ) -> Option<Vec2> {
+        let Some(NormalizedRenderTarget::Window(target)) = camera
+            .target
+            .normalize(self.primary_window.get_single().ok())
+        else {
+            return None;
+        };
+        if window.is_some() && window != Some(target.entity()) {
+            return None;
+        }
+        let Some(viewport) = camera.logical_viewport_rect() else {
+            return Some(pos);
+        };
+        if inside && !viewport.contains(pos) {
+            return None;
+        }
+        Some(pos - viewport.min)
+    }
+
+    /// Pointer position in ui coordinates of a joystick rendered by `camera`,
+    /// without camera the position is used as is
+    fn ui_position(
+        &self,
+        pos: Vec2,
+        window: Option<Entity>,
+        camera: Option<Entity>,
+        inside: bool,
+    ) -> Option<Vec2> {
+        let Some((_, camera, _)) = camera.and_then(|camera| self.cameras.get(camera).ok()) else {
+            return Some(pos);
+        };
+        let scale = self.ui_scale.as_ref().map_or(1., |scale| scale.0);
+        Some(self.viewport_position(pos, window, camera, inside)? / scale)
+    }
+
+    /// Same event with position in the space of a joystick,
+    /// world joysticks work in their local plane
+    pub(crate) fn joystick_event(
+        &self,
+        event: &InputEvent,
+        world: Option<(&VirtualJoystickWorldArea, &GlobalTransform)>,
+        camera: Option<Entity>,
+    ) -> Option<InputEvent> {
+        let window = event.window();
+        event.map_pos(|pos, start| match world {
+            Some((area, transform)) => self.world_position(pos, window, area, transform, start),
+            None => self.ui_position(pos, window, camera, start),
+        })
+    }
+
+    /// Pointer position on the local plane of a world joystick
+    fn world_position(
+        &self,
+        pos: Vec2,
+        window: Option<Entity>,
+        area: &VirtualJoystickWorldArea,
+        transform: &GlobalTransform,
+        inside: bool,
+    ) -> Option<Vec2> {
+        let (_, camera, camera_transform) = match area.camera {
+            Some(camera) => self.cameras.get(camera).ok()?,
+            None => self
+                .cameras
+                .iter()
+                .find(|(_, camera, _)| camera.is_active)?,
+        };
+        let pos = self.viewport_position(pos, window, camera, inside)?;
+        world_pointer_position(pos, transform, camera, camera_transform)
+    }
+}
+
+/// Inserted by [`crate::VirtualJoystickCorePlugin::headless`]
+#[derive(Resource)]
+pub(crate) struct VirtualJoystickHeadless;
+
+/// Reset knob to rest position, ending the current interaction
+fn release_knob(knob: &mut VirtualJoystickData) {
+    knob.id_drag = None;
+    knob.start_pos = Vec2::ZERO;
+    knob.current_pos = Vec2::ZERO;
+    knob.delta = Vec2::ZERO;
+    knob.current_pointer = None;
+    knob.velocity = Vec2::ZERO;
+    knob.acceleration = Vec2::ZERO;
+    knob.press_duration = 0.;
+}
+
+/// Event without position for joystick `node`, with the motion of `knob`
+fn joystick_event<S: VirtualJoystickID>(
+    node: &VirtualJoystickNode<S>,
+    knob: &VirtualJoystickData,
+    event: VirtualJoystickEventType,
+) -> VirtualJoystickEvent<S> {
+    VirtualJoystickEvent {
+        id: node.id.clone(),
+        event,
+        value: Vec2::ZERO,
+        delta: Vec2::ZERO,
+        axis: node.axis,
+        flick: None,
+        velocity: node.axis.handle_xy(-knob.velocity.x, knob.velocity.y),
+        acceleration: node
+            .axis
+            .handle_xy(-knob.acceleration.x, knob.acceleration.y),
+        press_duration: knob.press_duration,
+        stage: knob.stage,
+    }
+}
+
+/// Release the direction latched by [`VirtualJoystickLock`]
+fn unlock_knob<S: VirtualJoystickID>(
+    node: &VirtualJoystickNode<S>,
+    knob: &mut VirtualJoystickData,
+    send_values: &mut EventWriter<VirtualJoystickEvent<S>>,
+) {
+    knob.locked = false;
+    send_values.send(VirtualJoystickEvent {
+        delta: node.axis.handle_xy(-knob.delta.x, knob.delta.y),
+        ..joystick_event(node, knob, VirtualJoystickEventType::Unlocked)
+    });
+}
+
+/// Classify deflection of `knob` into a stage of `node`, released joysticks are idle
+fn update_stage<S: VirtualJoystickID>(
+    node: &VirtualJoystickNode<S>,
+    knob: &mut VirtualJoystickData,
+    send_values: &mut EventWriter<VirtualJoystickEvent<S>>,
+) {
+    let stage = if knob.id_drag.is_some() || knob.locked {
+        let magnitude = node.axis.handle(knob.delta).length().min(1.);
+        node.stages.stage(knob.stage, magnitude)
+    } else {
+        0
+    };
+    if stage != knob.stage {
+        knob.stage = stage;
+        send_values.send(VirtualJoystickEvent {
+            delta: node.axis.handle_xy(-knob.delta.x, knob.delta.y),
+            ..joystick_event(node, knob, VirtualJoystickEventType::StageChange)
+        });
+    }
+}
+
+/// Joystick shown and able to receive pointers
+pub(crate) fn is_joystick_visible(
+    visibility: &Visibility,
+    inherited_visibility: &InheritedVisibility,
+    view_visibility: &ViewVisibility,
+    headless: bool,
+) -> bool {
+    // Without renderer computed visibility is never updated
+    let computed_visible = headless || inherited_visibility.get() && view_visibility.get();
+    *visibility != Visibility::Hidden && computed_visible
 }
 
 fn is_some_and<T>(opt: Option<T>, cb: impl FnOnce(T) -> bool) -> bool {
@@ -24,62 +325,122 @@ fn is_some_and<T>(opt: Option<T>, cb: impl FnOnce(T) -> bool) -> bool {
     false
 }
 
+#[allow(clippy::type_complexity)]
 pub fn update_input<S: VirtualJoystickID>(
-    mut input_events: EventReader<InputEvent>,
+    time: Res<Time>,
+    pointers: Res<VirtualJoystickCapturedPointers>,
     mut send_values: EventWriter<VirtualJoystickEvent<S>>,
     mut joysticks: Query<(
+        Entity,
         &VirtualJoystickNode<S>,
         &Visibility,
         &InheritedVisibility,
         &ViewVisibility,
         &mut VirtualJoystickData,
+        Option<(&VirtualJoystickWorldArea, &GlobalTransform)>,
+        Option<&mut VirtualJoystickFixedInput>,
+        Has<VirtualJoystickDisabled>,
+        Option<&mut VirtualJoystickGestures>,
+        Option<&VirtualJoystickLock>,
     )>,
+    projection: PointerProjection,
+    headless: Option<Res<VirtualJoystickHeadless>>,
 ) {
-    let input_events = input_events.read().collect::<Vec<&InputEvent>>();
-
-    for (node, visibility, inherited_visibility, view_visibility, mut knob) in joysticks.iter_mut()
+    let now = time.elapsed_seconds();
+    let dt = time.delta_seconds();
+    for (
+        entity,
+        node,
+        visibility,
+        inherited_visibility,
+        view_visibility,
+        mut knob,
+        world,
+        mut fixed,
+        disabled,
+        mut gestures,
+        lock,
+    ) in joysticks.iter_mut()
     {
-        if visibility == Visibility::Hidden || !inherited_visibility.get() || !view_visibility.get()
-        {
+        if disabled {
+            if let Some(gestures) = gestures.as_mut() {
+                gestures.cancel();
+            }
+            if knob.id_drag.is_some() {
+                send_values.send(joystick_event(node, &knob, VirtualJoystickEventType::Up));
+                release_knob(&mut knob);
+                if let Some(fixed) = fixed.as_mut() {
+                    fixed.releases += 1;
+                }
+            }
+            if let Some(fixed) = fixed.as_mut() {
+                fixed.pressed = false;
+                fixed.axis = Vec2::ZERO;
+            }
+            if knob.locked {
+                unlock_knob(node, &mut knob, &mut send_values);
+                knob.delta = Vec2::ZERO;
+            }
+            update_stage(node, &mut knob, &mut send_values);
             continue;
         }
-        for event in &input_events {
-            match event {
-                InputEvent::StartDrag { id, pos, is_mouse } => {
-                    if let Some(current_iteraction_is_mouse) = &knob.current_iteraction_is_mouse {
-                        if *current_iteraction_is_mouse != *is_mouse {
-                            continue;
-                        }
+        if !is_joystick_visible(
+            visibility,
+            inherited_visibility,
+            view_visibility,
+            headless.is_some(),
+        ) {
+            continue;
+        }
+        let mut previous_delta = knob.delta;
+        let mut pressed_this_frame = false;
+        // Pointers are handed to joysticks by their owner, see `claim_pointers`
+        for (event, owner) in pointers.frame() {
+            if *owner != Some(entity) {
+                continue;
+            }
+            let Some(event) = projection.joystick_event(event, world, knob.camera) else {
+                continue;
+            };
+            match &event {
+                InputEvent::StartDrag {
+                    id, pos, pointer, ..
+                } => {
+                    if knob.locked {
+                        unlock_knob(node, &mut knob, &mut send_values);
                     }
-                    if knob.interactable_zone_rect.contains(*pos) && knob.id_drag.is_none()
-                        || is_some_and(knob.id_drag, |i| i != *id)
-                            && knob.interactable_zone_rect.contains(*pos)
-                    {
-                        knob.id_drag = Some(*id);
-                        knob.start_pos = *pos;
-                        knob.current_pos = *pos;
-                        knob.delta = Vec2::ZERO;
-                        knob.current_iteraction_is_mouse = Some(*is_mouse);
-                        send_values.send(VirtualJoystickEvent {
-                            id: node.id.clone(),
-                            event: VirtualJoystickEventType::Press,
-                            value: Vec2::ZERO,
-                            delta: Vec2::ZERO,
-                            axis: node.axis,
-                        });
+                    knob.id_drag = Some(*id);
+                    knob.start_pos = *pos;
+                    knob.current_pos = *pos;
+                    knob.delta = Vec2::ZERO;
+                    knob.current_pointer = Some(*pointer);
+                    knob.velocity = Vec2::ZERO;
+                    knob.acceleration = Vec2::ZERO;
+                    knob.press_duration = 0.;
+                    previous_delta = Vec2::ZERO;
+                    pressed_this_frame = true;
+                    if let Some(fixed) = fixed.as_mut() {
+                        fixed.presses += 1;
                     }
-                }
-                InputEvent::Dragging { id, pos, is_mouse } => {
-                    if let Some(current_iteraction_is_mouse) = &knob.current_iteraction_is_mouse {
-                        if *current_iteraction_is_mouse != *is_mouse {
-                            continue;
-                        }
+                    if let Some(gestures) = gestures.as_mut() {
+                        gestures.press(now, *pos);
                     }
-                    if !is_some_and(knob.id_drag, |i| i == *id) {
+                    send_values.send(joystick_event(node, &knob, VirtualJoystickEventType::Press));
+                }
+                InputEvent::Dragging {
+                    id, pos, pointer, ..
+                } => {
+                    if knob.id_drag.is_none() {
                         continue;
                     }
+                    // Owner may change while dragging when a finger hands off
+                    knob.id_drag = Some(*id);
+                    knob.current_pointer = Some(*pointer);
                     knob.current_pos = *pos;
-                    let half = knob.interactable_zone_rect.half_size();
+                    if let Some(gestures) = gestures.as_mut() {
+                        gestures.drag(now, *pos);
+                    }
+                    let half = knob.radius;
                     if node.behaviour == VirtualJoystickType::Dynamic {
                         let to_knob = knob.current_pos - knob.start_pos;
                         let distance_to_knob = to_knob.length();
@@ -93,120 +454,267 @@ pub fn update_input<S: VirtualJoystickID>(
                         d.x.signum() * d.x.abs().min(1.),
                         d.y.signum() * d.y.abs().min(1.),
                     );
+                    if let Some(lock) = lock {
+                        // Unclamped pointer offset from base, may be beyond the ring
+                        let offset = node.axis.handle_xy(-d.x, d.y);
+                        match (knob.locked, lock.is_locking(offset, node.behaviour)) {
+                            (false, true) => {
+                                knob.locked = true;
+                                send_values.send(VirtualJoystickEvent {
+                                    delta: node.axis.handle_xy(-knob.delta.x, knob.delta.y),
+                                    ..joystick_event(node, &knob, VirtualJoystickEventType::Locked)
+                                });
+                            }
+                            (true, false) => unlock_knob(node, &mut knob, &mut send_values),
+                            _ => {}
+                        }
+                    }
                 }
                 InputEvent::EndDrag {
-                    id,
-                    pos: _,
-                    is_mouse,
+                    id, pos, pointer, ..
+                }
+                | InputEvent::CancelDrag {
+                    id, pos, pointer, ..
                 } => {
-                    if let Some(current_iteraction_is_mouse) = &knob.current_iteraction_is_mouse {
-                        if *current_iteraction_is_mouse != *is_mouse {
-                            continue;
-                        }
+                    if matches!(knob.current_pointer, Some(current) if current != *pointer) {
+                        continue;
                     }
                     if !is_some_and(knob.id_drag, |i| i == *id) {
                         continue;
                     }
-                    knob.id_drag = None;
-                    knob.start_pos = Vec2::ZERO;
-                    knob.current_pos = Vec2::ZERO;
-                    knob.delta = Vec2::ZERO;
-                    knob.current_iteraction_is_mouse = None;
-                    send_values.send(VirtualJoystickEvent {
-                        id: node.id.clone(),
-                        event: VirtualJoystickEventType::Up,
-                        value: Vec2::ZERO,
-                        delta: Vec2::ZERO,
-                        axis: node.axis,
-                    });
+                    let canceled = matches!(event, InputEvent::CancelDrag { .. });
+                    send_values.send(joystick_event(
+                        node,
+                        &knob,
+                        if canceled {
+                            VirtualJoystickEventType::Cancel
+                        } else {
+                            VirtualJoystickEventType::Up
+                        },
+                    ));
+                    if let Some(gestures) = gestures.as_mut() {
+                        if canceled {
+                            gestures.cancel();
+                        } else {
+                            let flick = gestures.flick(now, *pos, node.axis);
+                            if flick.is_some() {
+                                // A flick moved too far and too fast to be a tap
+                                gestures.cancel();
+                            }
+                            for gesture in gestures.release(now, *pos) {
+                                send_values.send(joystick_event(node, &knob, gesture));
+                            }
+                            if let Some(flick) = flick {
+                                send_values.send(VirtualJoystickEvent {
+                                    flick: Some(flick),
+                                    ..joystick_event(node, &knob, VirtualJoystickEventType::Flick)
+                                });
+                            }
+                        }
+                    }
+                    if canceled && knob.locked {
+                        unlock_knob(node, &mut knob, &mut send_values);
+                    }
+                    // A locked joystick keeps its deflection until touched again
+                    let latched = knob.delta;
+                    release_knob(&mut knob);
+                    if knob.locked {
+                        knob.delta = latched;
+                    }
+                    if let Some(fixed) = fixed.as_mut() {
+                        fixed.releases += 1;
+                    }
                 }
             }
         }
 
+        // Derivatives of deflection over the frame
+        if knob.id_drag.is_some() && dt > 0. {
+            let velocity = (knob.delta - previous_delta) / dt;
+            knob.acceleration = (velocity - knob.velocity) / dt;
+            knob.velocity = velocity;
+            if !pressed_this_frame {
+                knob.press_duration += dt;
+            }
+        }
+
+        update_stage(node, &mut knob, &mut send_values);
+
+        if let Some(gestures) = gestures.as_mut() {
+            if gestures.long_press(now) {
+                send_values.send(joystick_event(
+                    node,
+                    &knob,
+                    VirtualJoystickEventType::LongPress,
+                ));
+            }
+        }
+
         // Send event
-        if (knob.delta.x.abs() >= knob.dead_zone || knob.delta.y.abs() >= knob.dead_zone)
-            && knob.id_drag.is_some()
-        {
+        let outside_dead_zone = (knob.delta.x.abs() >= knob.dead_zone
+            || knob.delta.y.abs() >= knob.dead_zone)
+            && (knob.id_drag.is_some() || knob.locked);
+        if outside_dead_zone {
             send_values.send(VirtualJoystickEvent {
-                id: node.id.clone(),
-                event: VirtualJoystickEventType::Drag,
                 value: node.axis.handle_xy(-knob.current_pos.x, knob.current_pos.y),
                 delta: node.axis.handle_xy(-knob.delta.x, knob.delta.y),
-                axis: node.axis,
+                ..joystick_event(node, &knob, VirtualJoystickEventType::Drag)
             });
         }
+
+        if let Some(fixed) = fixed.as_mut() {
+            fixed.pressed = knob.id_drag.is_some();
+            fixed.axis = if outside_dead_zone {
+                node.axis.handle_xy(-knob.delta.x, knob.delta.y)
+            } else {
+                Vec2::ZERO
+            };
+        }
     }
 }
 
-pub fn update_joystick(touch_events: Res<Touches>, mut send_values: EventWriter<InputEvent>) {
-    for e in touch_events.iter() {
-        let e: &bevy::input::touch::Touch = e;
-        let id = e.id();
-        let pos = e.position();
-
-        if touch_events.just_pressed(e.id()) {
-            // Start drag
-            send_values.send(InputEvent::StartDrag {
+pub fn update_joystick(
+    mut touch_input: EventReader<TouchInput>,
+    mut send_values: EventWriter<InputEvent>,
+) {
+    // Raw events keep every phase in order, even several in the same frame
+    for e in touch_input.read() {
+        let id = e.id;
+        let pos = e.position;
+        let window = Some(e.window);
+        send_values.send(match e.phase {
+            TouchPhase::Started => InputEvent::StartDrag {
                 id,
                 pos,
-                is_mouse: false,
-            });
-            continue;
-        }
-
-        if touch_events.just_released(e.id()) {
-            // End drag
-            send_values.send(InputEvent::EndDrag {
+                pointer: VirtualJoystickPointer::Touch,
+                window,
+            },
+            TouchPhase::Moved => InputEvent::Dragging {
                 id,
                 pos,
-                is_mouse: false,
-            });
-            continue;
-        }
-        // Dragging
-        send_values.send(InputEvent::Dragging {
-            id,
-            pos,
-            is_mouse: false,
+                pointer: VirtualJoystickPointer::Touch,
+                window,
+            },
+            TouchPhase::Ended => InputEvent::EndDrag {
+                id,
+                pos,
+                pointer: VirtualJoystickPointer::Touch,
+                window,
+            },
+            // Canceled by the system
+            TouchPhase::Canceled => InputEvent::CancelDrag {
+                id,
+                pos,
+                pointer: VirtualJoystickPointer::Touch,
+                window,
+            },
+        });
+    }
+}
+
+/// Mouse buttons sending pointers, chosen in [`crate::VirtualJoystickCorePlugin`]
+#[derive(Resource)]
+pub(crate) struct VirtualJoystickMouseButtons(pub(crate) Vec<MouseButton>);
+
+/// Mouse state kept between frames by [`update_joystick_by_mouse`]
+#[derive(Default)]
+pub struct MouseDragState {
+    /// Held buttons and the window their drag started in
+    pressed: Vec<(MouseButton, Entity)>,
+    positions: HashMap<Entity, Vec2>,
+}
+
+impl MouseDragState {
+    /// End the drag of `button` at the last position seen in its window
+    fn end_drag(&mut self, button: MouseButton, send_values: &mut EventWriter<InputEvent>) {
+        let Some(index) = self.pressed.iter().position(|(b, _)| *b == button) else {
+            return;
+        };
+        let (_, window) = self.pressed.remove(index);
+        send_values.send(InputEvent::EndDrag {
+            id: VirtualJoystickPointer::mouse_id(button),
+            pos: self.positions.get(&window).copied().unwrap_or(Vec2::ZERO),
+            pointer: VirtualJoystickPointer::Mouse(button),
+            window: Some(window),
         });
     }
 }
 
 pub fn update_joystick_by_mouse(
-    mouse_button_input: Res<ButtonInput<MouseButton>>,
+    buttons: Res<VirtualJoystickMouseButtons>,
     mut mousebtn_evr: EventReader<MouseButtonInput>,
+    mut cursor_evr: EventReader<CursorMoved>,
+    mut focus_evr: EventReader<WindowFocused>,
     mut send_values: EventWriter<InputEvent>,
-    windows: Query<&Window, With<PrimaryWindow>>,
+    mut state: Local<MouseDragState>,
+    windows: Query<&Window>,
 ) {
-    let window = windows.single();
-    let pos = window.cursor_position().unwrap_or(Vec2::ZERO);
+    // Buttons and cursor moves come in separate streams with no order between
+    // them, so every move of the frame is applied at the first button event.
+    // A press, move and release in the same frame starts at the final cursor
+    // position and the drag in between is lost
+    let mut moves = cursor_evr.read();
+    let mut flush_moves = |state: &mut MouseDragState,
+                           send_values: &mut EventWriter<InputEvent>| {
+        for moved in moves.by_ref() {
+            state.positions.insert(moved.window, moved.position);
+            // Dragging, once per held button
+            for (button, _) in &state.pressed {
+                send_values.send(InputEvent::Dragging {
+                    id: VirtualJoystickPointer::mouse_id(*button),
+                    pos: moved.position,
+                    pointer: VirtualJoystickPointer::Mouse(*button),
+                    window: Some(moved.window),
+                });
+            }
+        }
+    };
 
     for mousebtn in mousebtn_evr.read() {
-        // End drag
-        if mousebtn.button == MouseButton::Left && mousebtn.state == ButtonState::Released {
-            send_values.send(InputEvent::EndDrag {
-                id: 0,
-                pos,
-                is_mouse: true,
-            });
+        if !buttons.0.contains(&mousebtn.button) {
+            continue;
         }
-
-        // Start drag
-        if mousebtn.button == MouseButton::Left && mousebtn.state == ButtonState::Pressed {
-            send_values.send(InputEvent::StartDrag {
-                id: 0,
-                pos,
-                is_mouse: true,
-            });
+        flush_moves(&mut state, &mut send_values);
+        match mousebtn.state {
+            // End drag
+            ButtonState::Released => state.end_drag(mousebtn.button, &mut send_values),
+            ButtonState::Pressed => {
+                // Last known position, kept when the cursor leaves the window
+                let Some(pos) = state.positions.get(&mousebtn.window).copied().or_else(|| {
+                    windows
+                        .get(mousebtn.window)
+                        .ok()
+                        .and_then(|w| w.cursor_position())
+                }) else {
+                    // Start drag only where the cursor was seen
+                    continue;
+                };
+                state.positions.insert(mousebtn.window, pos);
+                // Release was missed outside the window
+                state.end_drag(mousebtn.button, &mut send_values);
+                state.pressed.push((mousebtn.button, mousebtn.window));
+                send_values.send(InputEvent::StartDrag {
+                    id: VirtualJoystickPointer::mouse_id(mousebtn.button),
+                    pos,
+                    pointer: VirtualJoystickPointer::Mouse(mousebtn.button),
+                    window: Some(mousebtn.window),
+                });
+            }
         }
     }
+    flush_moves(&mut state, &mut send_values);
 
-    // Dragging
-    if mouse_button_input.pressed(MouseButton::Left) {
-        send_values.send(InputEvent::Dragging {
-            id: 0,
-            pos,
-            is_mouse: true,
-        });
+    // Release is not received outside the window once it loses focus, every
+    // drag ends at the last position seen in the window it started in.
+    // Without focus loss a missed release ends the drag at the next press
+    if focus_evr.read().filter(|focus| !focus.focused).count() > 0 {
+        let held = state
+            .pressed
+            .iter()
+            .map(|(button, _)| *button)
+            .collect::<Vec<_>>();
+        for button in held {
+            state.end_drag(button, &mut send_values);
+        }
     }
 }
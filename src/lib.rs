@@ -1,27 +1,73 @@
 use std::{hash::Hash, marker::PhantomData};
 
 use bevy::{
+    ecs::schedule::{InternedScheduleLabel, ScheduleLabel},
+    input::InputSystem,
     prelude::*,
     reflect::TypePath,
     render::RenderApp,
+    time::TimeSystem,
+    transform::TransformSystem,
     ui::{RenderUiSystem, UiSystem},
 };
 
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
 mod behaviour;
+mod blocking;
+mod disabled;
+mod embedded;
+mod fixed;
+mod gesture;
 mod input;
+mod lock;
+mod ownership;
+mod pointer;
+mod record;
+mod stage;
+#[cfg(feature = "testing")]
+pub mod testing;
 mod ui;
 mod utils;
+mod world;
 
 pub use behaviour::{VirtualJoystickAxis, VirtualJoystickType};
-use input::{update_input, update_joystick, update_joystick_by_mouse, InputEvent};
+pub use disabled::{disable_joysticks, enable_joysticks, VirtualJoystickDisabled};
+pub use embedded::{default_background_image, default_knob_image};
+pub use fixed::VirtualJoystickFixedInput;
+pub use gesture::{VirtualJoystickFlick, VirtualJoystickGestures};
+pub use input::InputEvent;
+use input::{
+    update_input, update_joystick, update_joystick_by_mouse, VirtualJoystickHeadless,
+    VirtualJoystickMouseButtons,
+};
+pub use lock::VirtualJoystickLock;
+pub use ownership::{VirtualJoystickCapturedPointers, VirtualJoystickPriority};
+pub use pointer::{VirtualJoystickPointer, VirtualJoystickPointerFilter};
+pub use record::{
+    VirtualJoystickRecordPlugin, VirtualJoystickRecordedFrame, VirtualJoystickRecorder,
+    VirtualJoystickRecording, VirtualJoystickReplay,
+};
+pub use stage::VirtualJoystickStages;
 pub use ui::{
-    VirtualJoystickBundle, VirtualJoystickInteractionArea, VirtualJoystickNode,
-    VirtualJoystickUIBackground, VirtualJoystickUIKnob,
+    VirtualJoystickBundle, VirtualJoystickData, VirtualJoystickInteractionArea,
+    VirtualJoystickNode, VirtualJoystickShape, VirtualJoystickUIBackground, VirtualJoystickUIKnob,
+    VirtualJoystickUIStageRing, VirtualJoystickVisuals,
+};
+pub use utils::{create_default_joystick, create_joystick, create_procedural_joystick};
+pub use world::{
+    VirtualJoystickWorldArea, VirtualJoystickWorldBackground, VirtualJoystickWorldBundle,
+    VirtualJoystickWorldKnob,
 };
-pub use utils::create_joystick;
 
-use ui::{extract_joystick_node, VirtualJoystickData};
+use ui::{
+    extract_joystick_node, update_joystick_camera, update_joystick_shape_image,
+    update_joystick_stage_rings, update_joystick_visuals,
+};
 
+/// Joystick plugin for the ID type `S`, can be added once per ID type.
+/// Adds [`VirtualJoystickCorePlugin`] if missing
 #[derive(Default)]
 pub struct VirtualJoystickPlugin<S> {
     _marker: PhantomData<S>,
@@ -37,27 +83,218 @@ impl<S: Hash + Sync + Send + Clone + Default + Reflect + FromReflect + TypePath
 {
 }
 
-impl<S: VirtualJoystickID> Plugin for VirtualJoystickPlugin<S> {
-    fn build(&self, app: &mut bevy::prelude::App) {
+/// Shared part of joysticks that does not depend on the ID type, pointer
+/// collection from touches and mouse runs once whatever the number of
+/// [`VirtualJoystickPlugin`] added. Only needs to be added by hand to
+/// configure it before any [`VirtualJoystickPlugin`]
+///
+/// Usage:
+/// ```ignore
+/// app.add_plugins(VirtualJoystickCorePlugin::default().in_schedule(Update))
+///     .add_plugins(VirtualJoystickPlugin::<String>::default());
+/// ```
+pub struct VirtualJoystickCorePlugin {
+    /// See [`VirtualJoystickCorePlugin::headless`], applies to every [`VirtualJoystickPlugin`]
+    pub headless: bool,
+    /// Schedule running [`VirtualJoystickSystem::CollectPointers`],
+    /// [`VirtualJoystickSystem::ProcessJoysticks`] and
+    /// [`VirtualJoystickSystem::EmitEvents`], [`PreUpdate`] by default
+    pub schedule: InternedScheduleLabel,
+    /// Mouse buttons dragging joysticks, [`MouseButton::Left`] by default.
+    /// Each joystick can restrict them with [`VirtualJoystickPointerFilter`]
+    pub mouse_buttons: Vec<MouseButton>,
+}
+
+impl Default for VirtualJoystickCorePlugin {
+    fn default() -> Self {
+        Self {
+            headless: false,
+            schedule: PreUpdate.intern(),
+            mouse_buttons: vec![MouseButton::Left],
+        }
+    }
+}
+
+impl VirtualJoystickCorePlugin {
+    /// Plugin for apps without window or renderer, like servers or tests
+    /// using `MinimalPlugins`. Ui layout and visibility are not computed,
+    /// so only [`Visibility::Hidden`] disables a joystick and the interaction
+    /// area must be set with [`VirtualJoystickBundle::set_interactable_zone`].
+    /// Pointers are sent as [`InputEvent`]. Add it before any [`VirtualJoystickPlugin`]
+    ///
+    /// Usage:
+    /// ```ignore
+    /// app.add_plugins(VirtualJoystickCorePlugin::headless())
+    ///     .add_plugins(VirtualJoystickPlugin::<String>::default());
+    /// ```
+    pub fn headless() -> Self {
+        Self {
+            headless: true,
+            ..default()
+        }
+    }
+
+    /// Process joysticks in `schedule` instead of [`PreUpdate`]
+    pub fn in_schedule(mut self, schedule: impl ScheduleLabel) -> Self {
+        self.schedule = schedule.intern();
+        self
+    }
+
+    /// Mouse buttons dragging joysticks instead of [`MouseButton::Left`]
+    pub fn with_mouse_buttons(mut self, buttons: impl IntoIterator<Item = MouseButton>) -> Self {
+        self.mouse_buttons = buttons.into_iter().collect();
+        self
+    }
+}
+
+/// Labels of joystick systems, run in this order in the schedule of
+/// [`VirtualJoystickCorePlugin`] except `Layout` which runs in [`PostUpdate`]
+///
+/// Usage:
+/// ```ignore
+/// app.add_systems(PreUpdate, move_player.after(VirtualJoystickSystem::EmitEvents))
+///     .add_systems(PreUpdate, send_pointers.before(VirtualJoystickSystem::ProcessJoysticks));
+/// ```
+#[derive(SystemSet, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VirtualJoystickSystem {
+    /// Touches, mouse and replays are sent as [`InputEvent`]
+    CollectPointers,
+    /// Joysticks are updated from [`InputEvent`], [`VirtualJoystickData`]
+    /// and [`VirtualJoystickFixedInput`] are current after this set
+    ProcessJoysticks,
+    /// Every [`VirtualJoystickEvent`] of the frame has been sent after this set
+    EmitEvents,
+    /// Knob and background nodes are placed and styled, before ui layout
+    Layout,
+}
+
+/// Schedule chosen in [`VirtualJoystickCorePlugin`]
+#[derive(Resource)]
+pub(crate) struct VirtualJoystickSchedule(pub(crate) InternedScheduleLabel);
+
+impl Plugin for VirtualJoystickCorePlugin {
+    fn build(&self, app: &mut App) {
+        embedded::embed_default_assets(app);
+
         app.register_type::<VirtualJoystickInteractionArea>()
-            .register_type::<VirtualJoystickNode<S>>()
             .register_type::<VirtualJoystickData>()
             .register_type::<VirtualJoystickAxis>()
             .register_type::<VirtualJoystickType>()
             .register_type::<VirtualJoystickEventType>()
-            .add_event::<VirtualJoystickEvent<S>>()
+            .register_type::<VirtualJoystickShape>()
+            .register_type::<VirtualJoystickVisuals>()
+            .register_type::<VirtualJoystickWorldArea>()
+            .register_type::<VirtualJoystickWorldKnob>()
+            .register_type::<VirtualJoystickWorldBackground>()
+            .register_type::<VirtualJoystickFixedInput>()
+            .register_type::<VirtualJoystickDisabled>()
+            .register_type::<VirtualJoystickPointer>()
+            .register_type::<VirtualJoystickPointerFilter>()
+            .register_type::<VirtualJoystickPriority>()
+            .register_type::<VirtualJoystickGestures>()
+            .register_type::<VirtualJoystickFlick>()
+            .register_type::<VirtualJoystickUIStageRing>()
+            .register_type::<VirtualJoystickLock>()
             .add_event::<InputEvent>()
-            .add_systems(PreUpdate, update_joystick.before(update_input::<S>))
+            .init_resource::<VirtualJoystickCapturedPointers>()
+            .insert_resource(VirtualJoystickSchedule(self.schedule))
+            .insert_resource(VirtualJoystickMouseButtons(self.mouse_buttons.clone()))
+            .configure_sets(
+                self.schedule,
+                (
+                    VirtualJoystickSystem::CollectPointers,
+                    VirtualJoystickSystem::ProcessJoysticks,
+                    VirtualJoystickSystem::EmitEvents,
+                )
+                    .chain(),
+            )
+            .configure_sets(
+                PostUpdate,
+                VirtualJoystickSystem::Layout
+                    .before(UiSystem::Layout)
+                    .before(TransformSystem::TransformPropagate),
+            )
+            .add_systems(
+                self.schedule,
+                (
+                    (
+                        update_joystick
+                            .run_if(resource_exists::<Events<TouchInput>>)
+                            .run_if(record::no_replay),
+                        update_joystick_by_mouse
+                            .after(InputSystem)
+                            .run_if(resource_exists::<ButtonInput<MouseButton>>)
+                            .run_if(resource_exists::<Events<CursorMoved>>)
+                            .run_if(record::no_replay),
+                        record::replay_input
+                            .after(update_joystick)
+                            .after(update_joystick_by_mouse),
+                    )
+                        .in_set(VirtualJoystickSystem::CollectPointers),
+                    (
+                        world::update_world_joystick_area,
+                        update_joystick_camera,
+                        ownership::claim_pointers
+                            .after(world::update_world_joystick_area)
+                            .after(update_joystick_camera),
+                    )
+                        .in_set(VirtualJoystickSystem::ProcessJoysticks),
+                    (
+                        ownership::prune_pointer_owners,
+                        blocking::block_captured_interaction
+                            .after(ownership::prune_pointer_owners)
+                            .after(UiSystem::Focus),
+                    )
+                        .in_set(VirtualJoystickSystem::EmitEvents),
+                ),
+            )
             .add_systems(
-                PreUpdate,
-                update_joystick_by_mouse.before(update_input::<S>),
+                PostUpdate,
+                (update_joystick_shape_image, update_joystick_visuals)
+                    .in_set(VirtualJoystickSystem::Layout),
+            )
+            .add_systems(First, record::replay_time.before(TimeSystem))
+            .add_systems(FixedLast, fixed::reset_fixed_input);
+
+        if self.headless {
+            app.insert_resource(VirtualJoystickHeadless);
+        }
+    }
+}
+
+impl<S: VirtualJoystickID> Plugin for VirtualJoystickPlugin<S> {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        if !app.is_plugin_added::<VirtualJoystickCorePlugin>() {
+            app.add_plugins(VirtualJoystickCorePlugin::default());
+        }
+        let schedule = app.world.resource::<VirtualJoystickSchedule>().0;
+
+        app.register_type::<VirtualJoystickNode<S>>()
+            .add_event::<VirtualJoystickEvent<S>>()
+            .add_systems(
+                schedule,
+                update_input::<S>
+                    .after(ownership::claim_pointers)
+                    .in_set(VirtualJoystickSystem::ProcessJoysticks),
             )
-            .add_systems(PreUpdate, update_input::<S>)
             .add_systems(
                 PostUpdate,
-                joystick_image_node_system::<S>.before(UiSystem::Layout),
+                world::update_world_joystick_transforms::<S>.in_set(VirtualJoystickSystem::Layout),
             );
 
+        if app.world.contains_resource::<VirtualJoystickHeadless>() {
+            return;
+        }
+
+        app.add_systems(
+            PostUpdate,
+            (
+                joystick_image_node_system::<S>,
+                update_joystick_stage_rings::<S>.after(joystick_image_node_system::<S>),
+            )
+                .in_set(VirtualJoystickSystem::Layout),
+        );
+
         let Ok(render_app) = app.get_sub_app_mut(RenderApp) else {
             return;
         };
@@ -68,45 +305,76 @@ impl<S: VirtualJoystickID> Plugin for VirtualJoystickPlugin<S> {
     }
 }
 
+#[allow(clippy::type_complexity)]
 fn joystick_image_node_system<S: VirtualJoystickID>(
-    interaction_area: Query<&Node, With<VirtualJoystickInteractionArea>>,
-    mut joystick: Query<(
-        &Transform,
-        &VirtualJoystickNode<S>,
-        &mut VirtualJoystickData,
-    )>,
+    background: Query<&Node, With<VirtualJoystickUIBackground>>,
+    mut joystick: Query<
+        (
+            &Node,
+            &Transform,
+            &VirtualJoystickNode<S>,
+            &mut VirtualJoystickData,
+            Option<&Children>,
+        ),
+        With<VirtualJoystickInteractionArea>,
+    >,
 ) {
-    let interaction_area = interaction_area
-        .iter()
-        .map(|node| node.size())
-        .collect::<Vec<Vec2>>();
-
-    for (i, (j_pos, data, mut knob)) in joystick.iter_mut().enumerate() {
+    for (node, j_pos, data, mut knob, children) in joystick.iter_mut() {
         let j_pos = j_pos.translation.truncate();
-        let Some(size) = interaction_area.get(i) else {
-            return;
-        };
-        let interaction_area = Rect::from_center_size(j_pos, *size);
+        let interaction_area = Rect::from_center_size(j_pos, node.size());
+        let background_size = children
+            .into_iter()
+            .flatten()
+            .find_map(|child| background.get(*child).ok())
+            .map(|background| background.size())
+            .filter(|size| size.x > 0. && size.y > 0.);
         knob.dead_zone = data.dead_zone;
         knob.interactable_zone_rect = interaction_area;
+        knob.radius = background_size.unwrap_or(node.size()) / 2.;
     }
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 #[reflect]
 pub enum VirtualJoystickEventType {
     Press,
     Drag,
     Up,
+    /// Interaction interrupted by the system (canceled touch),
+    /// the joystick is reset like on [`VirtualJoystickEventType::Up`]
+    Cancel,
+    /// Quick press and release without moving, see [`VirtualJoystickGestures`]
+    Tap,
+    /// Second tap shortly after a tap, sent after its [`VirtualJoystickEventType::Tap`]
+    DoubleTap,
+    /// Held without moving, see [`VirtualJoystickGestures`]
+    LongPress,
+    /// Released while moving fast, see [`VirtualJoystickEvent::flick`]
+    Flick,
+    /// Deflection entered another stage, see [`VirtualJoystickEvent::stage`]
+    StageChange,
+    /// Direction latched, see [`VirtualJoystickLock`]. [`VirtualJoystickEventType::Drag`]
+    /// keeps being sent with the latched axis after release
+    Locked,
+    /// Latched direction released by touching the joystick again,
+    /// moving back from the lock area or disabling the joystick
+    Unlocked,
 }
 
-#[derive(Event, Debug)]
+#[derive(Event, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
 pub struct VirtualJoystickEvent<S: VirtualJoystickID> {
     id: S,
     event: VirtualJoystickEventType,
     value: Vec2,
     delta: Vec2,
     axis: VirtualJoystickAxis,
+    flick: Option<VirtualJoystickFlick>,
+    velocity: Vec2,
+    acceleration: Vec2,
+    press_duration: f32,
+    stage: usize,
 }
 
 impl<S: VirtualJoystickID> VirtualJoystickEvent<S> {
@@ -129,6 +397,31 @@ impl<S: VirtualJoystickID> VirtualJoystickEvent<S> {
         &self.delta
     }
 
+    /// Change of [`VirtualJoystickEvent::axis`] per second
+    pub fn velocity(&self) -> &Vec2 {
+        &self.velocity
+    }
+
+    /// Change of [`VirtualJoystickEvent::velocity`] per second
+    pub fn acceleration(&self) -> &Vec2 {
+        &self.acceleration
+    }
+
+    /// Seconds since the joystick was pressed
+    pub fn press_duration(&self) -> f32 {
+        self.press_duration
+    }
+
+    /// Stage of deflection, see [`VirtualJoystickStages`]
+    pub fn stage(&self) -> usize {
+        self.stage
+    }
+
+    /// Direction and speed of a [`VirtualJoystickEventType::Flick`]
+    pub fn flick(&self) -> Option<&VirtualJoystickFlick> {
+        self.flick.as_ref()
+    }
+
     /// Return the Type of Joystick Event
     pub fn get_type(&self) -> VirtualJoystickEventType {
         self.event
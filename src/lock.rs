@@ -0,0 +1,55 @@
+use bevy::prelude::*;
+
+use crate::VirtualJoystickType;
+
+/// Latch the direction of a joystick when dragged past the ring or into a
+/// lock icon, the joystick keeps its deflection after release until it is
+/// touched again. Sent as [`crate::VirtualJoystickEventType::Locked`] and
+/// [`crate::VirtualJoystickEventType::Unlocked`], while locked
+/// [`crate::VirtualJoystickData::locked`] is set and the latched deflection
+/// stays in [`crate::VirtualJoystickData::delta`]
+///
+/// Usage:
+/// ```ignore
+/// // auto run when dragged to 1.5 times the knob radius
+/// cmd.entity(joystick).insert(VirtualJoystickLock {
+///     distance: Some(1.5),
+///     ..default()
+/// });
+///
+/// // or when dragged into a lock icon drawn above the stick
+/// cmd.entity(joystick).insert(VirtualJoystickLock {
+///     distance: None,
+///     icon: Some(Rect::from_center_size(Vec2::new(0., 1.6), Vec2::splat(0.6))),
+/// });
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickLock {
+    /// Distance of the pointer from the base relative to the knob radius
+    /// beyond which the direction is latched, None disables it.
+    /// Ignored by [`VirtualJoystickType::Dynamic`] joysticks whose base
+    /// follows the pointer, use `icon` for them
+    pub distance: Option<f32>,
+    /// Area around the base in knob radii, x to the right and y up,
+    /// latching the direction while the pointer is inside
+    pub icon: Option<Rect>,
+}
+
+impl Default for VirtualJoystickLock {
+    fn default() -> Self {
+        Self {
+            distance: Some(1.3),
+            icon: None,
+        }
+    }
+}
+
+impl VirtualJoystickLock {
+    /// Pointer at `offset` from the base, in knob radii with y up, latches the direction
+    pub(crate) fn is_locking(&self, offset: Vec2, behaviour: VirtualJoystickType) -> bool {
+        let follows = behaviour == VirtualJoystickType::Dynamic;
+        matches!(self.distance, Some(distance) if !follows && offset.length() >= distance)
+            || matches!(self.icon, Some(icon) if icon.contains(offset))
+    }
+}
@@ -0,0 +1,242 @@
+use bevy::prelude::*;
+
+use crate::{
+    blocking::UiPointerBlockers,
+    input::{is_joystick_visible, PointerProjection, VirtualJoystickHeadless},
+    InputEvent, VirtualJoystickData, VirtualJoystickDisabled, VirtualJoystickPointer,
+    VirtualJoystickPointerFilter, VirtualJoystickWorldArea,
+};
+
+/// How a joystick claims pointers pressed on its interaction area
+#[derive(Component, Clone, Copy, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickPriority {
+    /// When interaction areas overlap the highest priority takes the pointer,
+    /// then the one on top in ui stacking order, ui joysticks before world ones
+    pub priority: i32,
+    /// Another pointer pressed on the area takes over the current drag
+    pub allow_stealing: bool,
+    /// When the driving finger lifts while another free finger is down
+    /// on the area, that finger keeps driving instead of releasing
+    pub allow_handoff: bool,
+}
+
+/// Ownership registry of pointers, each pointer drives at most one joystick
+/// until released. Updated in [`crate::VirtualJoystickSystem::ProcessJoysticks`],
+/// use it to ignore these pointers in world picking or camera controls
+///
+/// Usage:
+/// ```ignore
+/// fn pick(captured: Res<VirtualJoystickCapturedPointers>, touches: Res<Touches>) {
+///     for touch in touches.iter_just_pressed() {
+///         if captured.is_captured(touch.id(), VirtualJoystickPointer::Touch) {
+///             continue;
+///         }
+///         // pick in world
+///     }
+/// }
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct VirtualJoystickCapturedPointers {
+    owners: Vec<((u64, VirtualJoystickPointer), Entity)>,
+    /// Latest position of every pressed pointer, as a drag event
+    pressed: Vec<InputEvent>,
+    /// Pointer events of the frame with the joystick owning their pointer
+    frame: Vec<(InputEvent, Option<Entity>)>,
+}
+
+impl VirtualJoystickCapturedPointers {
+    /// Joystick dragged by a pointer
+    pub fn owner(&self, id: u64, pointer: VirtualJoystickPointer) -> Option<Entity> {
+        self.owners
+            .iter()
+            .find(|(key, _)| *key == (id, pointer))
+            .map(|(_, owner)| *owner)
+    }
+
+    pub fn is_captured(&self, id: u64, pointer: VirtualJoystickPointer) -> bool {
+        self.owner(id, pointer).is_some()
+    }
+
+    /// Mouse drags a joystick with any button
+    pub fn is_mouse_captured(&self) -> bool {
+        self.owners
+            .iter()
+            .any(|((_, pointer), _)| matches!(pointer, VirtualJoystickPointer::Mouse(_)))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (u64, VirtualJoystickPointer)> + '_ {
+        self.owners.iter().map(|(key, _)| *key)
+    }
+
+    pub(crate) fn frame(&self) -> &[(InputEvent, Option<Entity>)] {
+        &self.frame
+    }
+
+    fn set_pressed(&mut self, event: &InputEvent) {
+        let key = event.pointer();
+        self.pressed.retain(|other| other.pointer() != key);
+        self.pressed.push(event.to_dragging());
+    }
+
+    /// Pressed pointers not driving any joystick
+    fn free(&self) -> impl Iterator<Item = &InputEvent> + '_ {
+        self.pressed.iter().filter(|event| {
+            let (id, pointer) = event.pointer();
+            !self.is_captured(id, pointer)
+        })
+    }
+
+    fn dragged_by(&self, joystick: Entity) -> Option<(u64, VirtualJoystickPointer)> {
+        self.owners
+            .iter()
+            .find(|(_, owner)| *owner == joystick)
+            .map(|(key, _)| *key)
+    }
+}
+
+/// Give each new pointer to one joystick following [`VirtualJoystickPriority`]
+/// and hand off joysticks to free pointers
+#[allow(clippy::type_complexity)]
+pub(crate) fn claim_pointers(
+    mut input_events: EventReader<InputEvent>,
+    captured: ResMut<VirtualJoystickCapturedPointers>,
+    joysticks: Query<
+        (
+            Entity,
+            &VirtualJoystickData,
+            &Visibility,
+            &InheritedVisibility,
+            &ViewVisibility,
+            Option<(&VirtualJoystickWorldArea, &GlobalTransform)>,
+            Option<&VirtualJoystickPointerFilter>,
+            Option<&VirtualJoystickPriority>,
+        ),
+        Without<VirtualJoystickDisabled>,
+    >,
+    projection: PointerProjection,
+    blockers: UiPointerBlockers,
+    headless: Option<Res<VirtualJoystickHeadless>>,
+) {
+    let captured = captured.into_inner();
+    captured.frame.clear();
+    for event in input_events.read() {
+        let key = event.pointer();
+        let owner = match event {
+            InputEvent::StartDrag { .. } => {
+                let owner = joysticks
+                    .iter()
+                    .filter_map(
+                        |(entity, data, visibility, inherited, view, world, filter, priority)| {
+                            if !is_joystick_visible(visibility, inherited, view, headless.is_some())
+                                || matches!(filter, Some(filter) if !filter.accepts(key.1))
+                            {
+                                return None;
+                            }
+                            let InputEvent::StartDrag { pos, .. } =
+                                projection.joystick_event(event, world, data.camera)?
+                            else {
+                                return None;
+                            };
+                            if !data.interactable_zone_rect.contains(pos) {
+                                return None;
+                            }
+                            // Buttons stacked above claim the pointer,
+                            // world joysticks are below every ui node
+                            let blocked = match world {
+                                Some(_) => matches!(
+                                    projection.joystick_event(event, None, data.camera),
+                                    Some(InputEvent::StartDrag { pos, .. })
+                                        if blockers.is_blocked(None, pos)
+                                ),
+                                None => blockers.is_blocked(Some(entity), pos),
+                            };
+                            if blocked {
+                                return None;
+                            }
+                            let priority = priority.copied().unwrap_or_default();
+                            let dragged_by = captured.dragged_by(entity);
+                            if matches!(dragged_by, Some(other) if other != key)
+                                && !priority.allow_stealing
+                            {
+                                return None;
+                            }
+                            Some((priority.priority, blockers.stack_index(entity), entity))
+                        },
+                    )
+                    .max_by_key(|(priority, stack_index, _)| (*priority, *stack_index))
+                    .map(|(_, _, entity)| entity);
+                if let Some(owner) = owner {
+                    // A stolen joystick is no longer driven by its previous pointer
+                    captured
+                        .owners
+                        .retain(|(other, joystick)| *other != key && *joystick != owner);
+                    captured.owners.push((key, owner));
+                }
+                captured.set_pressed(event);
+                owner
+            }
+            InputEvent::Dragging { .. } => {
+                captured.set_pressed(event);
+                captured.owner(key.0, key.1)
+            }
+            InputEvent::EndDrag { .. } | InputEvent::CancelDrag { .. } => {
+                let owner = captured.owner(key.0, key.1);
+                captured.owners.retain(|(other, _)| *other != key);
+                captured.pressed.retain(|other| other.pointer() != key);
+                let handoff = match (event, owner) {
+                    (InputEvent::EndDrag { .. }, Some(owner)) => joysticks
+                        .get(owner)
+                        .ok()
+                        .filter(|(.., priority)| matches!(priority, Some(p) if p.allow_handoff))
+                        .and_then(|(_, data, .., world, filter, _)| {
+                            captured
+                                .free()
+                                .find(|free| {
+                                    !matches!(filter, Some(filter) if !filter.accepts(free.pointer().1))
+                                        && matches!(
+                                            projection.joystick_event(free, world, data.camera),
+                                            Some(InputEvent::Dragging { pos, .. })
+                                                if data.interactable_zone_rect.contains(pos)
+                                        )
+                                })
+                                .cloned()
+                        })
+                        .map(|free| (owner, free)),
+                    _ => None,
+                };
+                if let Some((owner, free)) = handoff {
+                    // The lifted finger is dropped and the free one keeps dragging
+                    captured.frame.push((event.clone(), None));
+                    captured.owners.push((free.pointer(), owner));
+                    captured.frame.push((free, Some(owner)));
+                    continue;
+                }
+                owner
+            }
+        };
+        captured.frame.push((event.clone(), owner));
+    }
+}
+
+/// Forget pointers of joysticks released for other reasons (disabled, despawned)
+pub(crate) fn prune_pointer_owners(
+    joysticks: Query<&VirtualJoystickData>,
+    mut captured: ResMut<VirtualJoystickCapturedPointers>,
+) {
+    let stale = captured.owners.iter().any(|((id, pointer), owner)| {
+        !matches!(
+            joysticks.get(*owner),
+            Ok(data) if data.id_drag == Some(*id) && data.current_pointer == Some(*pointer)
+        )
+    });
+    if !stale {
+        return;
+    }
+    captured.owners.retain(|((id, pointer), owner)| {
+        matches!(
+            joysticks.get(*owner),
+            Ok(data) if data.id_drag == Some(*id) && data.current_pointer == Some(*pointer)
+        )
+    });
+}
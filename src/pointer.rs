@@ -0,0 +1,91 @@
+use bevy::prelude::*;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Device driving a pointer of [`crate::InputEvent`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Reflect)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum VirtualJoystickPointer {
+    Touch,
+    /// Mouse dragged with this button held
+    Mouse(MouseButton),
+    /// Stylus, bevy does not tell pens from fingers so these are only sent by hand
+    Pen,
+    /// Any other source sent by hand, like a gamepad driven cursor
+    Custom(u32),
+}
+
+impl VirtualJoystickPointer {
+    /// Pointer id used for a mouse button, distinct for each button
+    pub(crate) fn mouse_id(button: MouseButton) -> u64 {
+        match button {
+            MouseButton::Left => 0,
+            MouseButton::Right => 1,
+            MouseButton::Middle => 2,
+            MouseButton::Back => 3,
+            MouseButton::Forward => 4,
+            MouseButton::Other(n) => 5 + n as u64,
+        }
+    }
+}
+
+/// Pointer kinds accepted by a joystick, joysticks without this
+/// component accept every pointer
+///
+/// Usage:
+/// ```ignore
+/// // Camera stick dragged with the right button, left click stays free
+/// cmd.entity(joystick)
+///     .insert(VirtualJoystickPointerFilter::mouse_only([MouseButton::Right]));
+/// ```
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickPointerFilter {
+    pub touch: bool,
+    /// Mouse buttons must also be listed in
+    /// [`crate::VirtualJoystickCorePlugin::mouse_buttons`] to be sent
+    pub mouse_buttons: Vec<MouseButton>,
+    pub pen: bool,
+    pub custom: Vec<u32>,
+}
+
+impl Default for VirtualJoystickPointerFilter {
+    fn default() -> Self {
+        Self {
+            touch: true,
+            mouse_buttons: vec![MouseButton::Left],
+            pen: true,
+            custom: Vec::new(),
+        }
+    }
+}
+
+impl VirtualJoystickPointerFilter {
+    /// Only fingers and pens
+    pub fn touch_only() -> Self {
+        Self {
+            mouse_buttons: Vec::new(),
+            ..default()
+        }
+    }
+
+    /// Only the mouse with any of `buttons` held
+    pub fn mouse_only(buttons: impl IntoIterator<Item = MouseButton>) -> Self {
+        Self {
+            touch: false,
+            mouse_buttons: buttons.into_iter().collect(),
+            pen: false,
+            custom: Vec::new(),
+        }
+    }
+
+    pub fn accepts(&self, pointer: VirtualJoystickPointer) -> bool {
+        match pointer {
+            VirtualJoystickPointer::Touch => self.touch,
+            VirtualJoystickPointer::Mouse(button) => self.mouse_buttons.contains(&button),
+            VirtualJoystickPointer::Pen => self.pen,
+            VirtualJoystickPointer::Custom(id) => self.custom.contains(&id),
+        }
+    }
+}
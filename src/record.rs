@@ -0,0 +1,209 @@
+use std::{collections::VecDeque, marker::PhantomData, time::Duration};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    InputEvent, VirtualJoystickCorePlugin, VirtualJoystickEvent, VirtualJoystickID,
+    VirtualJoystickSchedule, VirtualJoystickSystem,
+};
+
+/// Pointer input and joystick output of one frame
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VirtualJoystickRecordedFrame<S: VirtualJoystickID> {
+    /// Seconds since previous frame, used as frame duration on replay
+    pub delta_seconds: f32,
+    pub input: Vec<InputEvent>,
+    pub output: Vec<VirtualJoystickEvent<S>>,
+}
+
+/// Session captured by [`VirtualJoystickRecorder`], serializable with the `serde` feature
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct VirtualJoystickRecording<S: VirtualJoystickID> {
+    pub frames: Vec<VirtualJoystickRecordedFrame<S>>,
+}
+
+impl<S: VirtualJoystickID> Default for VirtualJoystickRecording<S> {
+    fn default() -> Self {
+        Self { frames: Vec::new() }
+    }
+}
+
+/// Records pointer input and joystick output per frame while started
+///
+/// Usage:
+/// ```ignore
+/// fn start(mut recorder: ResMut<VirtualJoystickRecorder<String>>) {
+///     recorder.start();
+/// }
+///
+/// fn stop(mut recorder: ResMut<VirtualJoystickRecorder<String>>) {
+///     if let Some(recording) = recorder.stop() {
+///         let file = ron::to_string(&recording).unwrap();
+///     }
+/// }
+/// ```
+#[derive(Resource)]
+pub struct VirtualJoystickRecorder<S: VirtualJoystickID> {
+    recording: Option<VirtualJoystickRecording<S>>,
+}
+
+impl<S: VirtualJoystickID> Default for VirtualJoystickRecorder<S> {
+    fn default() -> Self {
+        Self { recording: None }
+    }
+}
+
+impl<S: VirtualJoystickID> VirtualJoystickRecorder<S> {
+    /// Start a new recording, discarding the current one
+    pub fn start(&mut self) {
+        self.recording = Some(default());
+    }
+
+    /// Stop recording and return the session
+    pub fn stop(&mut self) -> Option<VirtualJoystickRecording<S>> {
+        self.recording.take()
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recording.is_some()
+    }
+}
+
+/// Feeds recorded pointer input back to joysticks one frame at a time,
+/// touch and mouse input is ignored until the replay finishes.
+/// [`Time`] advances by the recorded frame durations while replaying so
+/// gestures and knob velocity match the recording at any frame rate.
+/// Windows are not kept between sessions so replayed pointers match
+/// joysticks on any window
+///
+/// Usage:
+/// ```ignore
+/// let recording: VirtualJoystickRecording<String> = ron::from_str(&file).unwrap();
+/// cmd.insert_resource(VirtualJoystickReplay::new(&recording));
+/// ```
+#[derive(Resource, Clone, Debug, Default)]
+pub struct VirtualJoystickReplay {
+    frames: VecDeque<(f32, Vec<InputEvent>)>,
+}
+
+impl VirtualJoystickReplay {
+    pub fn new<S: VirtualJoystickID>(recording: &VirtualJoystickRecording<S>) -> Self {
+        Self {
+            frames: recording
+                .frames
+                .iter()
+                .map(|frame| {
+                    (
+                        frame.delta_seconds,
+                        frame.input.iter().map(InputEvent::without_window).collect(),
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    /// All frames were replayed
+    pub fn is_finished(&self) -> bool {
+        self.frames.is_empty()
+    }
+}
+
+/// Adds [`VirtualJoystickRecorder`] and [`VirtualJoystickReplay`] support.
+/// Adds [`VirtualJoystickCorePlugin`] if missing
+#[derive(Default)]
+pub struct VirtualJoystickRecordPlugin<S> {
+    _marker: PhantomData<S>,
+}
+
+impl<S: VirtualJoystickID> Plugin for VirtualJoystickRecordPlugin<S> {
+    fn build(&self, app: &mut App) {
+        if !app.is_plugin_added::<VirtualJoystickCorePlugin>() {
+            app.add_plugins(VirtualJoystickCorePlugin::default());
+        }
+        let schedule = app.world.resource::<VirtualJoystickSchedule>().0;
+        app.init_resource::<VirtualJoystickRecorder<S>>()
+            .add_systems(
+                schedule,
+                record_input::<S>.in_set(VirtualJoystickSystem::EmitEvents),
+            )
+            .add_systems(Last, record_output::<S>);
+    }
+}
+
+/// Live input is not collected while a replay is running
+pub(crate) fn no_replay(replay: Option<Res<VirtualJoystickReplay>>) -> bool {
+    replay.map_or(true, |replay| replay.is_finished())
+}
+
+pub(crate) fn replay_input(
+    replay: Option<ResMut<VirtualJoystickReplay>>,
+    mut send_values: EventWriter<InputEvent>,
+) {
+    let Some(mut replay) = replay else {
+        return;
+    };
+    if let Some((_, frame)) = replay.frames.pop_front() {
+        send_values.send_batch(frame);
+    }
+}
+
+/// Advance [`Time`] by the recorded duration of the frame about to be
+/// replayed, the previous update strategy is restored once replay finishes
+pub(crate) fn replay_time(
+    replay: Option<Res<VirtualJoystickReplay>>,
+    strategy: Option<ResMut<TimeUpdateStrategy>>,
+    mut previous: Local<Option<TimeUpdateStrategy>>,
+) {
+    let Some(mut strategy) = strategy else {
+        return;
+    };
+    match replay.as_ref().and_then(|replay| replay.frames.front()) {
+        Some((delta_seconds, _)) => {
+            let recorded =
+                TimeUpdateStrategy::ManualDuration(Duration::from_secs_f32(delta_seconds.max(0.)));
+            let live = std::mem::replace(&mut *strategy, recorded);
+            previous.get_or_insert(live);
+        }
+        None => {
+            if let Some(live) = previous.take() {
+                *strategy = live;
+            }
+        }
+    }
+}
+
+fn record_input<S: VirtualJoystickID>(
+    time: Res<Time>,
+    mut input_events: EventReader<InputEvent>,
+    mut recorder: ResMut<VirtualJoystickRecorder<S>>,
+) {
+    let Some(recording) = recorder.recording.as_mut() else {
+        input_events.clear();
+        return;
+    };
+    recording.frames.push(VirtualJoystickRecordedFrame {
+        delta_seconds: time.delta_seconds(),
+        input: input_events.read().cloned().collect(),
+        output: Vec::new(),
+    });
+}
+
+fn record_output<S: VirtualJoystickID>(
+    mut events: EventReader<VirtualJoystickEvent<S>>,
+    mut recorder: ResMut<VirtualJoystickRecorder<S>>,
+) {
+    let output = events.read().cloned().collect::<Vec<_>>();
+    let Some(frame) = recorder
+        .recording
+        .as_mut()
+        .and_then(|recording| recording.frames.last_mut())
+    else {
+        return;
+    };
+    frame.output.extend(output);
+}
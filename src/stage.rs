@@ -0,0 +1,77 @@
+use bevy::prelude::*;
+
+/// Stages of deflection like idle, walk and run, sent as
+/// [`crate::VirtualJoystickEventType::StageChange`] when the stage changes,
+/// see [`crate::VirtualJoystickEvent::stage`]
+///
+/// Usage:
+/// ```ignore
+/// VirtualJoystickNode {
+///     id: "Move".to_string(),
+///     // idle below 0.1, walk below 0.8, run above
+///     stages: VirtualJoystickStages::new([0.1, 0.8]).with_rings(Color::WHITE),
+///     ..default()
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Reflect)]
+#[reflect(Default)]
+pub struct VirtualJoystickStages {
+    /// Ascending magnitudes of deflection (0 to 1) entering each stage,
+    /// stage 0 is below the first threshold, empty disables stages
+    pub thresholds: Vec<f32>,
+    /// Magnitude below a threshold needed to leave its stage,
+    /// avoids flickering between stages around a threshold
+    pub hysteresis: f32,
+    /// Draw a ring on the background at every threshold except the
+    /// first one, which usually only separates idle from moving
+    pub ring_color: Option<Color>,
+}
+
+impl Default for VirtualJoystickStages {
+    fn default() -> Self {
+        Self {
+            thresholds: Vec::new(),
+            hysteresis: 0.05,
+            ring_color: None,
+        }
+    }
+}
+
+impl VirtualJoystickStages {
+    pub fn new(thresholds: impl IntoIterator<Item = f32>) -> Self {
+        Self {
+            thresholds: thresholds.into_iter().collect(),
+            ..default()
+        }
+    }
+
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis;
+        self
+    }
+
+    pub fn with_rings(mut self, color: Color) -> Self {
+        self.ring_color = Some(color);
+        self
+    }
+
+    /// Thresholds drawn as rings
+    pub(crate) fn rings(&self) -> &[f32] {
+        match self.ring_color {
+            Some(_) if !self.thresholds.is_empty() => &self.thresholds[1..],
+            _ => &[],
+        }
+    }
+
+    /// Stage of `magnitude` coming from stage `current`
+    pub fn stage(&self, current: usize, magnitude: f32) -> usize {
+        let mut stage = current.min(self.thresholds.len());
+        while stage < self.thresholds.len() && magnitude >= self.thresholds[stage] {
+            stage += 1;
+        }
+        while stage > 0 && magnitude < self.thresholds[stage - 1] - self.hysteresis {
+            stage -= 1;
+        }
+        stage
+    }
+}
@@ -0,0 +1,160 @@
+//! Helpers to test joystick driven logic in a headless [`App`]
+//!
+//! Usage:
+//! ```
+//! use bevy::prelude::*;
+//! use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+//!
+//! let mut driver = VirtualJoystickTestDriver::<String>::new();
+//! let joystick = driver.spawn_joystick(
+//!     VirtualJoystickNode {
+//!         id: "Move".to_string(),
+//!         behaviour: VirtualJoystickType::Fixed,
+//!         ..default()
+//!     },
+//!     Rect::new(0., 0., 100., 100.),
+//! );
+//! driver
+//!     .press(0, Vec2::new(50., 50.))
+//!     .move_along(0, [Vec2::new(75., 50.), Vec2::new(100., 50.)])
+//!     .release(0, Vec2::new(100., 50.));
+//!
+//! let events = driver.take_events();
+//! assert_eq!(events[0].get_type(), VirtualJoystickEventType::Press);
+//! assert_eq!(events.last().unwrap().get_type(), VirtualJoystickEventType::Up);
+//! assert!(driver.data(joystick).id_drag.is_none());
+//! ```
+use std::{marker::PhantomData, time::Duration};
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+
+use crate::{
+    InputEvent, VirtualJoystickBundle, VirtualJoystickCorePlugin, VirtualJoystickData,
+    VirtualJoystickEvent, VirtualJoystickID, VirtualJoystickNode, VirtualJoystickPlugin,
+    VirtualJoystickPointer,
+};
+
+/// Time advanced on each frame of the driver
+pub const FRAME_TIME: Duration = Duration::from_nanos(16_666_667);
+
+#[derive(Resource)]
+struct RecordedEvents<S: VirtualJoystickID>(Vec<VirtualJoystickEvent<S>>);
+
+fn record_events<S: VirtualJoystickID>(
+    mut events: EventReader<VirtualJoystickEvent<S>>,
+    mut recorded: ResMut<RecordedEvents<S>>,
+) {
+    recorded.0.extend(events.read().cloned());
+}
+
+/// Headless app with [`VirtualJoystickPlugin`] where pointers are injected by hand,
+/// every pointer action runs one frame of [`FRAME_TIME`]
+pub struct VirtualJoystickTestDriver<S: VirtualJoystickID> {
+    /// The driven app, add your own systems to test them with the joysticks
+    pub app: App,
+    _marker: PhantomData<S>,
+}
+
+impl<S: VirtualJoystickID> Default for VirtualJoystickTestDriver<S> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S: VirtualJoystickID> VirtualJoystickTestDriver<S> {
+    pub fn new() -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(VirtualJoystickCorePlugin::headless())
+            .add_plugins(VirtualJoystickPlugin::<S>::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(FRAME_TIME))
+            .insert_resource(RecordedEvents::<S>(Vec::new()))
+            .add_systems(Last, record_events::<S>);
+        Self {
+            app,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Spawn a joystick with interaction area `zone` in window coordinates
+    pub fn spawn_joystick(&mut self, node: VirtualJoystickNode<S>, zone: Rect) -> Entity {
+        self.app
+            .world
+            .spawn(VirtualJoystickBundle::new(node).set_interactable_zone(zone))
+            .id()
+    }
+
+    /// Send a pointer event and run one frame
+    pub fn send(&mut self, event: InputEvent) -> &mut Self {
+        self.app.world.send_event(event);
+        self.update()
+    }
+
+    /// Run one frame without input
+    pub fn update(&mut self) -> &mut Self {
+        self.app.update();
+        self
+    }
+
+    /// Touch down with finger `id` at `pos`
+    pub fn press(&mut self, id: u64, pos: Vec2) -> &mut Self {
+        self.send(InputEvent::StartDrag {
+            id,
+            pos,
+            pointer: VirtualJoystickPointer::Touch,
+            window: None,
+        })
+    }
+
+    /// Move finger `id` to `pos`
+    pub fn move_to(&mut self, id: u64, pos: Vec2) -> &mut Self {
+        self.send(InputEvent::Dragging {
+            id,
+            pos,
+            pointer: VirtualJoystickPointer::Touch,
+            window: None,
+        })
+    }
+
+    /// Move finger `id` through every point of `path`, one frame per point
+    pub fn move_along(&mut self, id: u64, path: impl IntoIterator<Item = Vec2>) -> &mut Self {
+        for pos in path {
+            self.move_to(id, pos);
+        }
+        self
+    }
+
+    /// Lift finger `id` at `pos`
+    pub fn release(&mut self, id: u64, pos: Vec2) -> &mut Self {
+        self.send(InputEvent::EndDrag {
+            id,
+            pos,
+            pointer: VirtualJoystickPointer::Touch,
+            window: None,
+        })
+    }
+
+    /// Cancel finger `id` at `pos`, like a system gesture interrupting the touch
+    pub fn cancel(&mut self, id: u64, pos: Vec2) -> &mut Self {
+        self.send(InputEvent::CancelDrag {
+            id,
+            pos,
+            pointer: VirtualJoystickPointer::Touch,
+            window: None,
+        })
+    }
+
+    /// Joystick events emitted since the last call
+    pub fn take_events(&mut self) -> Vec<VirtualJoystickEvent<S>> {
+        std::mem::take(&mut self.app.world.resource_mut::<RecordedEvents<S>>().0)
+    }
+
+    /// Current state of a joystick
+    pub fn data(&self, joystick: Entity) -> VirtualJoystickData {
+        self.app
+            .world
+            .get::<VirtualJoystickData>(joystick)
+            .cloned()
+            .expect("entity is not a joystick")
+    }
+}
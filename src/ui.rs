@@ -1,4 +1,5 @@
 mod bundles;
+mod shape;
 mod systems;
 
 pub use bundles::*;
@@ -3,7 +3,10 @@ use bevy::{prelude::*, ui::RelativeCursorPosition};
 #[cfg(feature = "inspect")]
 use bevy_inspector_egui::prelude::*;
 
-use crate::{VirtualJoystickAxis, VirtualJoystickID, VirtualJoystickType};
+use crate::{
+    VirtualJoystickAxis, VirtualJoystickFixedInput, VirtualJoystickID, VirtualJoystickPointer,
+    VirtualJoystickStages, VirtualJoystickType,
+};
 
 #[derive(Component, Clone, Debug, Default, Reflect)]
 #[reflect(Component, Default)]
@@ -23,6 +26,90 @@ pub struct VirtualJoystickUIKnob;
 #[cfg_attr(feature = "inspect", reflect(InspectorOptions))]
 pub struct VirtualJoystickUIBackground;
 
+/// Ring drawn on the background at a threshold of [`VirtualJoystickStages`],
+/// spawned and sized by the plugin
+#[derive(Component, Copy, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickUIStageRing {
+    /// Magnitude of the threshold, relative to background size
+    pub threshold: f32,
+}
+
+/// Procedural look for a knob or background node, the image
+/// is generated at runtime so no image asset is required
+#[derive(Component, Clone, Copy, Debug, Default, PartialEq, Reflect)]
+#[reflect(Component, Default)]
+pub enum VirtualJoystickShape {
+    /// Filled circle
+    #[default]
+    Circle,
+    /// Circle outline
+    Ring {
+        /// Thickness of ring relative to radius (0 to 1)
+        thickness: f32,
+        /// Draw direction arrows inside the ring for this axis
+        arrows: Option<VirtualJoystickAxis>,
+    },
+}
+
+/// Visual feedback for knob and background of a joystick,
+/// colors of both nodes are captured when this component is added
+/// and then multiplied by the current tint and opacity
+#[derive(Component, Clone, Debug, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickVisuals {
+    /// Tint applied to knob and background while pressed
+    pub pressed_tint: Color,
+    /// Scale of knob while pressed
+    pub pressed_scale: f32,
+    /// Seconds without interaction before fading, None never fades
+    pub idle_fade_after: Option<f32>,
+    /// Opacity once faded by inactivity
+    pub idle_opacity: f32,
+    /// Hide knob and background until touched
+    pub auto_hide: bool,
+    /// Opacity change per second, 0 changes instantly
+    pub fade_speed: f32,
+    /// Tint applied to knob and background while [`crate::VirtualJoystickDisabled`]
+    pub disabled_tint: Color,
+}
+
+impl Default for VirtualJoystickVisuals {
+    fn default() -> Self {
+        Self {
+            pressed_tint: Color::WHITE,
+            pressed_scale: 1.,
+            idle_fade_after: None,
+            idle_opacity: 0.3,
+            auto_hide: false,
+            fade_speed: 0.,
+            disabled_tint: Color::rgba(0.5, 0.5, 0.5, 0.6),
+        }
+    }
+}
+
+impl VirtualJoystickVisuals {
+    /// Hidden until touched, use with [`VirtualJoystickType::Floating`]
+    /// and a large interaction area so the joystick appears centered at
+    /// the touch point and hides again on release
+    pub fn appear_at_touch() -> Self {
+        Self {
+            auto_hide: true,
+            ..default()
+        }
+    }
+}
+
+/// Runtime state of [`VirtualJoystickVisuals`]
+#[derive(Component, Clone, Debug, Default)]
+pub(crate) struct VirtualJoystickVisualState {
+    pub(crate) knob_color: Option<Color>,
+    pub(crate) background_color: Option<Color>,
+    pub(crate) ring_color: Option<Color>,
+    pub(crate) idle_time: f32,
+    pub(crate) opacity: Option<f32>,
+}
+
 #[derive(Bundle, Debug, Default)]
 pub struct VirtualJoystickBundle<S: VirtualJoystickID> {
     /// Describes the size of the node
@@ -44,6 +131,7 @@ pub struct VirtualJoystickBundle<S: VirtualJoystickID> {
     /// Indicates the depth at which the node should appear in the UI
     pub(crate) z_index: ZIndex,
     pub(crate) knob_data: VirtualJoystickData,
+    pub(crate) fixed_input: VirtualJoystickFixedInput,
     pub(crate) cursor_pos: RelativeCursorPosition,
 }
 
@@ -58,6 +146,8 @@ pub struct VirtualJoystickNode<S: VirtualJoystickID> {
     pub axis: VirtualJoystickAxis,
     /// Define the behaviour of joystick
     pub behaviour: VirtualJoystickType,
+    /// Stages of deflection, none by default
+    pub stages: VirtualJoystickStages,
 }
 
 #[derive(Component, Clone, Debug, Default, Reflect)]
@@ -68,11 +158,25 @@ pub struct VirtualJoystickData {
     pub start_pos: Vec2,
     pub current_pos: Vec2,
     pub delta: Vec2,
+    /// Change of `delta` per second
+    pub velocity: Vec2,
+    /// Change of `velocity` per second
+    pub acceleration: Vec2,
+    /// Seconds since press, 0 when not pressed
+    pub press_duration: f32,
     pub interactable_zone_rect: Rect,
-    /// None means no current interaction<br/>
-    /// Some(false) means current interaction is touch<br/>
-    /// Some(true) means current interaction is mouse
-    pub current_iteraction_is_mouse: Option<bool>,
+    /// Max distance of knob from base in each axis, half size of
+    /// background node or of interactable zone without background
+    pub radius: Vec2,
+    /// Camera rendering the joystick ui, pointers on other windows
+    /// or outside its viewport are routed to other joysticks
+    pub camera: Option<Entity>,
+    /// Pointer kind of current interaction, None means no current interaction
+    pub current_pointer: Option<VirtualJoystickPointer>,
+    /// Current stage of deflection, see [`crate::VirtualJoystickStages`]
+    pub stage: usize,
+    /// Direction latched by [`crate::VirtualJoystickLock`], kept after release
+    pub locked: bool,
 }
 
 impl<S: VirtualJoystickID> VirtualJoystickBundle<S> {
@@ -107,4 +211,12 @@ impl<S: VirtualJoystickID> VirtualJoystickBundle<S> {
         self.z_index = z_index;
         self
     }
+
+    /// Interaction area in window coordinates, only needed in
+    /// headless mode where it is not computed from ui layout
+    pub fn set_interactable_zone(mut self, rect: Rect) -> Self {
+        self.knob_data.interactable_zone_rect = rect;
+        self.knob_data.radius = rect.half_size();
+        self
+    }
 }
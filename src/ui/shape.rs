@@ -0,0 +1,92 @@
+use bevy::{
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::{VirtualJoystickAxis, VirtualJoystickShape};
+
+/// Size in pixels of generated images, UI scales them to node size
+const SHAPE_RESOLUTION: u32 = 128;
+
+/// Rasterize the shape into a white image, color is applied
+/// by node tint ([`BackgroundColor`])
+pub(crate) fn shape_image(shape: &VirtualJoystickShape) -> Image {
+    let size = SHAPE_RESOLUTION as f32;
+    let radius = size / 2.;
+    let center = Vec2::splat(radius);
+
+    let arrows = match shape {
+        VirtualJoystickShape::Circle => Vec::new(),
+        VirtualJoystickShape::Ring { thickness, arrows } => arrows
+            .map(|axis| arrow_triangles(axis, radius * (1. - thickness.clamp(0., 1.))))
+            .unwrap_or_default(),
+    };
+
+    let mut data = Vec::with_capacity((SHAPE_RESOLUTION * SHAPE_RESOLUTION * 4) as usize);
+    for y in 0..SHAPE_RESOLUTION {
+        for x in 0..SHAPE_RESOLUTION {
+            let p = Vec2::new(x as f32 + 0.5, y as f32 + 0.5) - center;
+            let distance = p.length();
+            // one pixel of anti aliasing on each edge
+            let outer = (radius - distance).clamp(0., 1.);
+            let coverage = match shape {
+                VirtualJoystickShape::Circle => outer,
+                VirtualJoystickShape::Ring { thickness, .. } => {
+                    let inner_radius = radius * (1. - thickness.clamp(0., 1.));
+                    let ring = outer.min((distance - inner_radius).clamp(0., 1.));
+                    if arrows.iter().any(|triangle| in_triangle(p, triangle)) {
+                        1.
+                    } else {
+                        ring
+                    }
+                }
+            };
+            data.extend_from_slice(&[255, 255, 255, (coverage * 255.) as u8]);
+        }
+    }
+
+    Image::new(
+        Extent3d {
+            width: SHAPE_RESOLUTION,
+            height: SHAPE_RESOLUTION,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::default(),
+    )
+}
+
+/// Triangles pointing outward just inside the ring, image space is y down
+fn arrow_triangles(axis: VirtualJoystickAxis, inner_radius: f32) -> Vec<[Vec2; 3]> {
+    let directions: &[Vec2] = match axis {
+        VirtualJoystickAxis::Both => &[Vec2::X, Vec2::NEG_X, Vec2::Y, Vec2::NEG_Y],
+        VirtualJoystickAxis::Horizontal => &[Vec2::X, Vec2::NEG_X],
+        VirtualJoystickAxis::Vertical => &[Vec2::Y, Vec2::NEG_Y],
+    };
+    let tip_distance = inner_radius * 0.9;
+    let height = inner_radius * 0.2;
+    let half_width = inner_radius * 0.18;
+
+    directions
+        .iter()
+        .map(|dir| {
+            let side = dir.perp() * half_width;
+            let base = *dir * (tip_distance - height);
+            [*dir * tip_distance, base + side, base - side]
+        })
+        .collect()
+}
+
+fn in_triangle(p: Vec2, [a, b, c]: &[Vec2; 3]) -> bool {
+    let d1 = (p - *b).perp_dot(*a - *b);
+    let d2 = (p - *c).perp_dot(*b - *c);
+    let d3 = (p - *a).perp_dot(*c - *a);
+    let has_neg = d1 < 0. || d2 < 0. || d3 < 0.;
+    let has_pos = d1 > 0. || d2 > 0. || d3 > 0.;
+    !(has_neg && has_pos)
+}
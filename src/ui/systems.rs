@@ -1,17 +1,250 @@
-use bevy::{prelude::*, render::Extract, ui::ExtractedUiNodes};
+use bevy::{
+    prelude::*,
+    render::Extract,
+    ui::{DefaultUiCamera, ExtractedUiNodes},
+};
 
 use crate::{
-    VirtualJoystickID, VirtualJoystickNode, VirtualJoystickType, VirtualJoystickUIBackground,
-    VirtualJoystickUIKnob,
+    VirtualJoystickDisabled, VirtualJoystickID, VirtualJoystickInteractionArea,
+    VirtualJoystickNode, VirtualJoystickShape, VirtualJoystickType, VirtualJoystickUIBackground,
+    VirtualJoystickUIKnob, VirtualJoystickUIStageRing, VirtualJoystickVisuals,
 };
 
-use super::VirtualJoystickData;
+use super::{shape::shape_image, VirtualJoystickData, VirtualJoystickVisualState};
+
+/// Generate images for nodes using [`VirtualJoystickShape`],
+/// images are shared between nodes with the same shape
+pub fn update_joystick_shape_image(
+    images: Option<ResMut<Assets<Image>>>,
+    mut cache: Local<Vec<(VirtualJoystickShape, Handle<Image>)>>,
+    mut shapes: Query<(&VirtualJoystickShape, &mut UiImage), Changed<VirtualJoystickShape>>,
+) {
+    let Some(mut images) = images else {
+        return;
+    };
+    for (shape, mut image) in &mut shapes {
+        let handle = match cache.iter().find(|(s, _)| s == shape) {
+            Some((_, handle)) => handle.clone(),
+            None => {
+                let handle = images.add(shape_image(shape));
+                cache.push((*shape, handle.clone()));
+                handle
+            }
+        };
+        image.texture = handle;
+    }
+}
+
+/// Spawn a [`VirtualJoystickUIStageRing`] for each ring of [`crate::VirtualJoystickStages`]
+/// when the joystick node changes, rings are sized from the knob radius
+#[allow(clippy::type_complexity)]
+pub fn update_joystick_stage_rings<S: VirtualJoystickID>(
+    mut cmd: Commands,
+    joysticks: Query<
+        (
+            Entity,
+            Ref<VirtualJoystickNode<S>>,
+            &VirtualJoystickData,
+            Option<&Children>,
+        ),
+        With<VirtualJoystickInteractionArea>,
+    >,
+    mut rings: Query<(&VirtualJoystickUIStageRing, &mut Style)>,
+) {
+    for (entity, node, data, children) in &joysticks {
+        let children = children.into_iter().flatten();
+        if node.is_changed() {
+            for ring in children.filter(|child| rings.contains(**child)) {
+                cmd.entity(*ring).despawn_recursive();
+            }
+            let color = node.stages.ring_color.unwrap_or(Color::WHITE);
+            let spawned = node
+                .stages
+                .rings()
+                .iter()
+                .map(|threshold| {
+                    let (width, height) = ring_size(data, *threshold);
+                    cmd.spawn((
+                        VirtualJoystickUIStageRing {
+                            threshold: *threshold,
+                        },
+                        VirtualJoystickShape::Ring {
+                            thickness: 0.05,
+                            arrows: None,
+                        },
+                        ImageBundle {
+                            style: Style {
+                                position_type: PositionType::Absolute,
+                                width,
+                                height,
+                                ..default()
+                            },
+                            background_color: color.into(),
+                            ..default()
+                        },
+                    ))
+                    .id()
+                })
+                .collect::<Vec<_>>();
+            // Behind the background and knob
+            cmd.entity(entity).insert_children(0, &spawned);
+            continue;
+        }
+        for child in children {
+            let Ok((ring, mut style)) = rings.get_mut(*child) else {
+                continue;
+            };
+            let (width, height) = ring_size(data, ring.threshold);
+            if style.width != width || style.height != height {
+                style.width = width;
+                style.height = height;
+            }
+        }
+    }
+}
+
+fn ring_size(data: &VirtualJoystickData, threshold: f32) -> (Val, Val) {
+    let size = data.radius * 2. * threshold;
+    (Val::Px(size.x), Val::Px(size.y))
+}
+
+/// Find camera of ui joysticks, the [`TargetCamera`] of their
+/// root node or the default ui camera
+pub fn update_joystick_camera(
+    default_ui_camera: DefaultUiCamera,
+    parents: Query<&Parent>,
+    target_cameras: Query<&TargetCamera>,
+    mut joysticks: Query<(Entity, &mut VirtualJoystickData), With<Node>>,
+) {
+    let default_camera = default_ui_camera.get();
+    for (entity, mut data) in &mut joysticks {
+        let root = parents.iter_ancestors(entity).last().unwrap_or(entity);
+        let camera = target_cameras
+            .get(root)
+            .ok()
+            .map(TargetCamera::entity)
+            .or(default_camera);
+        if data.camera != camera {
+            data.camera = camera;
+        }
+    }
+}
+
+/// Apply [`VirtualJoystickVisuals`] to knob and background nodes
+#[allow(clippy::type_complexity)]
+pub fn update_joystick_visuals(
+    mut cmd: Commands,
+    time: Res<Time>,
+    mut joysticks: Query<(
+        Entity,
+        &VirtualJoystickVisuals,
+        &VirtualJoystickData,
+        &Children,
+        Option<&mut VirtualJoystickVisualState>,
+        Has<VirtualJoystickDisabled>,
+    )>,
+    mut knobs: Query<
+        (&mut BackgroundColor, &mut Transform),
+        (
+            With<VirtualJoystickUIKnob>,
+            Without<VirtualJoystickUIBackground>,
+        ),
+    >,
+    mut backgrounds: Query<
+        &mut BackgroundColor,
+        (
+            With<VirtualJoystickUIBackground>,
+            Without<VirtualJoystickUIKnob>,
+        ),
+    >,
+    mut rings: Query<
+        &mut BackgroundColor,
+        (
+            With<VirtualJoystickUIStageRing>,
+            Without<VirtualJoystickUIKnob>,
+            Without<VirtualJoystickUIBackground>,
+        ),
+    >,
+) {
+    for (entity, visuals, data, children, state, disabled) in &mut joysticks {
+        let mut new_state = None;
+        let state = match state {
+            Some(state) => state.into_inner(),
+            None => new_state.insert(VirtualJoystickVisualState::default()),
+        };
+
+        let pressed = data.id_drag.is_some();
+        // A locked joystick keeps moving without being pressed
+        if pressed || data.locked {
+            state.idle_time = 0.;
+        } else {
+            state.idle_time += time.delta_seconds();
+        }
+        let target = if pressed || data.locked {
+            1.
+        } else if visuals.auto_hide {
+            0.
+        } else if matches!(visuals.idle_fade_after, Some(after) if state.idle_time >= after) {
+            visuals.idle_opacity
+        } else {
+            1.
+        };
+        let opacity = match state.opacity {
+            Some(opacity) if visuals.fade_speed > 0. => {
+                let step = visuals.fade_speed * time.delta_seconds();
+                opacity + (target - opacity).clamp(-step, step)
+            }
+            _ => target,
+        };
+        state.opacity = Some(opacity);
+        let tint = if disabled {
+            visuals.disabled_tint
+        } else if pressed {
+            visuals.pressed_tint
+        } else {
+            Color::WHITE
+        };
+
+        for child in children {
+            if let Ok((mut color, mut transform)) = knobs.get_mut(*child) {
+                let base = *state.knob_color.get_or_insert(color.0);
+                color.0 = apply_tint(base, tint, opacity);
+                let scale = if pressed { visuals.pressed_scale } else { 1. };
+                transform.scale = Vec3::new(scale, scale, 1.);
+            } else if let Ok(mut color) = backgrounds.get_mut(*child) {
+                let base = *state.background_color.get_or_insert(color.0);
+                color.0 = apply_tint(base, tint, opacity);
+            } else if let Ok(mut color) = rings.get_mut(*child) {
+                let base = *state.ring_color.get_or_insert(color.0);
+                color.0 = apply_tint(base, tint, opacity);
+            }
+        }
+
+        if let Some(state) = new_state {
+            cmd.entity(entity).insert(state);
+        }
+    }
+}
+
+fn apply_tint(base: Color, tint: Color, opacity: f32) -> Color {
+    let [r, g, b, a] = base.as_rgba_f32();
+    let [tr, tg, tb, ta] = tint.as_rgba_f32();
+    Color::rgba(r * tr, g * tg, b * tb, a * ta * opacity)
+}
 
 #[allow(clippy::type_complexity)]
 pub fn extract_joystick_node<S: VirtualJoystickID>(
     mut extracted_uinodes: ResMut<ExtractedUiNodes>,
     knob_ui_query: Extract<Query<(Entity, &Parent), With<VirtualJoystickUIKnob>>>,
-    bg_ui_query: Extract<Query<(Entity, &Parent), With<VirtualJoystickUIBackground>>>,
+    bg_ui_query: Extract<
+        Query<
+            (Entity, &Parent),
+            Or<(
+                With<VirtualJoystickUIBackground>,
+                With<VirtualJoystickUIStageRing>,
+            )>,
+        >,
+    >,
     uinode_query: Extract<
         Query<(
             &Node,
@@ -44,13 +277,13 @@ pub fn extract_joystick_node<S: VirtualJoystickID>(
                 continue;
             }
             let base_pos = get_base_pos(uinode, joystick_node.behaviour, data, global_transform);
-            let radius = uinode.size().x / 2.;
             // ui is y down, so we flip
-            let pos = -data.delta * radius;
+            let pos = -data.delta * data.radius;
             let knob_pos = base_pos + joystick_node.axis.handle_vec3(pos.extend(0.));
 
             extracted_uinodes.uinodes.entry(entity).and_modify(|node| {
-                node.transform = Mat4::from_translation(knob_pos);
+                // keep scale of node, only move it
+                node.transform.w_axis = knob_pos.extend(1.);
             });
         }
     }
@@ -76,7 +309,7 @@ pub fn extract_joystick_node<S: VirtualJoystickID>(
             }
             let pos = get_base_pos(uinode, joystick_node.behaviour, data, global_transform);
             extracted_uinodes.uinodes.entry(entity).and_modify(|node| {
-                node.transform = Mat4::from_translation(pos);
+                node.transform.w_axis = pos.extend(1.);
             });
         }
     }
@@ -1,12 +1,13 @@
 use bevy::prelude::*;
 
 use crate::{
+    embedded::{default_background_image, default_knob_image},
     VirtualJoystickBundle, VirtualJoystickID, VirtualJoystickInteractionArea, VirtualJoystickNode,
-    VirtualJoystickUIBackground, VirtualJoystickUIKnob,
+    VirtualJoystickShape, VirtualJoystickUIBackground, VirtualJoystickUIKnob,
 };
 
 /// This function is a simple helper to create a joystick
-/// Entity with all needed without complexity
+/// Entity with all needed without complexity, returns the spawned entity
 ///
 /// * `cmd`: bevy Commands, this required to spawn entity
 /// * `knob_img`: Handle image for joystick knob
@@ -20,7 +21,7 @@ use crate::{
 /// * `joystick_node_style`: bevy Style for joystick
 ///
 /// Usage:
-/// ```no_run
+/// ```ignore
 /// create_joystick(
 ///     cmd,
 ///     asset_server.load("Knob.png"),
@@ -35,6 +36,7 @@ use crate::{
 ///         id: "UniqueJoystick".to_string(),
 ///         axis: VirtualJoystickAxis::Both,
 ///         behaviour: VirtualJoystickType::Floating,
+///         ..default()
 ///     },
 ///     Style {
 ///         width: Val::Px(150.),
@@ -48,13 +50,14 @@ use crate::{
 /// ```
 ///
 /// if you not want use this function helper, you need do that
-/// ```no_run
+/// ```ignore
 /// cmd.spawn((
 ///     VirtualJoystickBundle::new(VirtualJoystickNode {
 ///         dead_zone: 0.,
 ///         id: "UniqueJoystick".to_string(),
 ///         axis: VirtualJoystickAxis::Both,
 ///         behaviour: VirtualJoystickType::Floating,
+///         ..default()
 ///     })
 ///     .set_style(Style {
 ///         width: Val::Px(150.),
@@ -108,41 +111,179 @@ pub fn create_joystick<I: VirtualJoystickID>(
     background_size: Vec2,
     joystick_node: VirtualJoystickNode<I>,
     joystick_node_style: Style,
-) {
+) -> Entity {
+    spawn_joystick(
+        cmd,
+        image_bundle(knob_img, knob_color, knob_size),
+        image_bundle(background_img, background_color, background_size),
+        (),
+        (),
+        interactable_area_color,
+        joystick_node,
+        joystick_node_style,
+    )
+}
+
+/// Same as [`create_joystick`] but knob and border are drawn
+/// procedurally (see [`VirtualJoystickShape`]), no image assets are needed
+///
+/// * `ring_thickness`: Thickness of border ring relative to its radius (0 to 1)
+/// * `arrows`: Draw direction arrows for the joystick axis inside the ring
+///
+/// Usage:
+/// ```ignore
+/// create_procedural_joystick(
+///     cmd,
+///     Some(Color::WHITE.with_a(0.8)),
+///     Some(Color::WHITE.with_a(0.5)),
+///     None,
+///     Vec2::new(75., 75.),
+///     Vec2::new(150., 150.),
+///     0.1,
+///     true,
+///     VirtualJoystickNode {
+///         dead_zone: 0.,
+///         id: "UniqueJoystick".to_string(),
+///         axis: VirtualJoystickAxis::Both,
+///         behaviour: VirtualJoystickType::Floating,
+///         ..default()
+///     },
+///     Style {
+///         width: Val::Px(150.),
+///         height: Val::Px(150.),
+///         position_type: PositionType::Absolute,
+///         left: Val::Percent(50.),
+///         bottom: Val::Percent(15.),
+///         ..default()
+///     },
+/// );
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn create_procedural_joystick<I: VirtualJoystickID>(
+    cmd: &mut Commands,
+    knob_color: Option<Color>,
+    background_color: Option<Color>,
+    interactable_area_color: Option<Color>,
+    knob_size: Vec2,
+    background_size: Vec2,
+    ring_thickness: f32,
+    arrows: bool,
+    joystick_node: VirtualJoystickNode<I>,
+    joystick_node_style: Style,
+) -> Entity {
+    let ring = VirtualJoystickShape::Ring {
+        thickness: ring_thickness,
+        arrows: arrows.then_some(joystick_node.axis),
+    };
+    spawn_joystick(
+        cmd,
+        image_bundle(Handle::default(), knob_color, knob_size),
+        image_bundle(Handle::default(), background_color, background_size),
+        VirtualJoystickShape::Circle,
+        ring,
+        interactable_area_color,
+        joystick_node,
+        joystick_node_style,
+    )
+}
+
+/// Same as [`create_joystick`] using the images embedded in this crate,
+/// the Horizontal, Vertical or Both variant is picked from the joystick axis
+///
+/// * `asset_server`: bevy AssetServer, used to load embedded images
+/// * `arrows`: Use the border image with direction arrows
+///
+/// Usage:
+/// ```ignore
+/// create_default_joystick(
+///     cmd,
+///     &asset_server,
+///     true,
+///     None,
+///     None,
+///     Some(Color::ORANGE_RED.with_a(0.3)),
+///     Vec2::new(75., 75.),
+///     Vec2::new(150., 150.),
+///     VirtualJoystickNode {
+///         dead_zone: 0.,
+///         id: "UniqueJoystick".to_string(),
+///         axis: VirtualJoystickAxis::Horizontal,
+///         behaviour: VirtualJoystickType::Fixed,
+///         ..default()
+///     },
+///     Style {
+///         width: Val::Px(150.),
+///         height: Val::Px(150.),
+///         position_type: PositionType::Absolute,
+///         left: Val::Percent(50.),
+///         bottom: Val::Percent(15.),
+///         ..default()
+///     },
+/// );
+/// ```
+#[allow(clippy::too_many_arguments)]
+pub fn create_default_joystick<I: VirtualJoystickID>(
+    cmd: &mut Commands,
+    asset_server: &AssetServer,
+    arrows: bool,
+    knob_color: Option<Color>,
+    background_color: Option<Color>,
+    interactable_area_color: Option<Color>,
+    knob_size: Vec2,
+    background_size: Vec2,
+    joystick_node: VirtualJoystickNode<I>,
+    joystick_node_style: Style,
+) -> Entity {
+    let axis = joystick_node.axis;
+    create_joystick(
+        cmd,
+        asset_server.load(default_knob_image(axis, false)),
+        asset_server.load(default_background_image(axis, arrows)),
+        knob_color,
+        background_color,
+        interactable_area_color,
+        knob_size,
+        background_size,
+        joystick_node,
+        joystick_node_style,
+    )
+}
+
+fn image_bundle(image: Handle<Image>, color: Option<Color>, size: Vec2) -> ImageBundle {
+    ImageBundle {
+        image: image.into(),
+        style: Style {
+            width: Val::Px(size.x),
+            height: Val::Px(size.y),
+            ..default()
+        },
+        background_color: color.unwrap_or(Color::WHITE).into(),
+        ..default()
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn spawn_joystick<I: VirtualJoystickID>(
+    cmd: &mut Commands,
+    knob: ImageBundle,
+    background: ImageBundle,
+    knob_extra: impl Bundle,
+    background_extra: impl Bundle,
+    interactable_area_color: Option<Color>,
+    joystick_node: VirtualJoystickNode<I>,
+    joystick_node_style: Style,
+) -> Entity {
     let mut spawn =
         cmd.spawn(VirtualJoystickBundle::new(joystick_node).set_style(joystick_node_style));
     let spawn = spawn
         .insert(VirtualJoystickInteractionArea)
         .with_children(|parent| {
-            parent.spawn((
-                VirtualJoystickUIKnob,
-                ImageBundle {
-                    image: knob_img.into(),
-                    style: Style {
-                        width: Val::Px(knob_size.x),
-                        height: Val::Px(knob_size.y),
-                        ..default()
-                    },
-                    background_color: knob_color.unwrap_or(Color::WHITE).into(),
-                    ..default()
-                },
-            ));
-            parent.spawn((
-                VirtualJoystickUIBackground,
-                ImageBundle {
-                    image: background_img.into(),
-                    style: Style {
-                        width: Val::Px(background_size.x),
-                        height: Val::Px(background_size.y),
-                        ..default()
-                    },
-                    background_color: background_color.unwrap_or(Color::WHITE).into(),
-                    ..default()
-                },
-            ));
+            parent.spawn((VirtualJoystickUIKnob, knob, knob_extra));
+            parent.spawn((VirtualJoystickUIBackground, background, background_extra));
         });
 
     if let Some(c) = interactable_area_color {
         spawn.insert(BackgroundColor(c));
     }
+    spawn.id()
 }
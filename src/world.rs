@@ -0,0 +1,182 @@
+use bevy::{
+    math::primitives::{Direction3d, Plane3d},
+    prelude::*,
+};
+
+#[cfg(feature = "inspect")]
+use bevy_inspector_egui::prelude::*;
+
+use crate::{
+    VirtualJoystickData, VirtualJoystickFixedInput, VirtualJoystickID, VirtualJoystickNode,
+    VirtualJoystickType,
+};
+
+/// Interaction area of a joystick placed in the world instead of the UI,
+/// the area lies on the local XY plane of the joystick entity
+#[derive(Component, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+pub struct VirtualJoystickWorldArea {
+    /// Size of area in local units, centered on the joystick entity
+    pub size: Vec2,
+    /// Max distance of knob from base in local units, None uses half of size
+    pub knob_radius: Option<f32>,
+    /// Camera used to project pointers, None uses the first active camera
+    pub camera: Option<Entity>,
+}
+
+/// Knob of a world joystick, any child with a [`Transform`]
+/// (sprite, mesh, ...) can be used
+#[derive(Component, Copy, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(feature = "inspect", derive(InspectorOptions))]
+#[cfg_attr(feature = "inspect", reflect(InspectorOptions))]
+pub struct VirtualJoystickWorldKnob;
+
+/// Background of a world joystick, any child with a [`Transform`]
+/// (sprite, mesh, ...) can be used
+#[derive(Component, Copy, Clone, Debug, Default, Reflect)]
+#[reflect(Component, Default)]
+#[cfg_attr(feature = "inspect", derive(InspectorOptions))]
+#[cfg_attr(feature = "inspect", reflect(InspectorOptions))]
+pub struct VirtualJoystickWorldBackground;
+
+/// Joystick placed in world space, spawn knob and background as
+/// children marked with [`VirtualJoystickWorldKnob`] and
+/// [`VirtualJoystickWorldBackground`]
+///
+/// Usage:
+/// ```ignore
+/// cmd.spawn(
+///     VirtualJoystickWorldBundle::new(
+///         VirtualJoystickNode {
+///             dead_zone: 0.,
+///             id: "Lever".to_string(),
+///             axis: VirtualJoystickAxis::Vertical,
+///             behaviour: VirtualJoystickType::Fixed,
+///             ..default()
+///         },
+///         VirtualJoystickWorldArea {
+///             size: Vec2::new(100., 100.),
+///             ..default()
+///         },
+///     )
+///     .set_transform(Transform::from_xyz(200., 0., 0.)),
+/// )
+/// .with_children(|parent| {
+///     parent.spawn((
+///         VirtualJoystickWorldKnob,
+///         SpriteBundle {
+///             texture: asset_server.load("Knob.png"),
+///             sprite: Sprite {
+///                 custom_size: Some(Vec2::new(50., 50.)),
+///                 ..default()
+///             },
+///             ..default()
+///         },
+///     ));
+/// });
+/// ```
+#[derive(Bundle, Debug, Default)]
+pub struct VirtualJoystickWorldBundle<S: VirtualJoystickID> {
+    pub(crate) joystick: VirtualJoystickNode<S>,
+    pub(crate) area: VirtualJoystickWorldArea,
+    pub(crate) knob_data: VirtualJoystickData,
+    pub(crate) fixed_input: VirtualJoystickFixedInput,
+    /// The transform of the joystick
+    pub(crate) transform: Transform,
+    /// The global transform of the joystick
+    pub(crate) global_transform: GlobalTransform,
+    /// Describes the visibility properties of the joystick
+    pub visibility: Visibility,
+    /// Inherited visibility of an entity.
+    pub inherited_visibility: InheritedVisibility,
+    /// Algorithmically-computed indication of whether an entity is visible and should be extracted for rendering
+    pub view_visibility: ViewVisibility,
+}
+
+impl<S: VirtualJoystickID> VirtualJoystickWorldBundle<S> {
+    pub fn new(joystick: VirtualJoystickNode<S>, area: VirtualJoystickWorldArea) -> Self {
+        Self {
+            joystick,
+            area,
+            ..default()
+        }
+    }
+
+    pub fn set_transform(mut self, transform: Transform) -> Self {
+        self.transform = transform;
+        self
+    }
+}
+
+/// Project a pointer position (relative to camera viewport) onto the
+/// local plane of a world joystick, returned y down like ui positions
+pub(crate) fn world_pointer_position(
+    pos: Vec2,
+    transform: &GlobalTransform,
+    camera: &Camera,
+    camera_transform: &GlobalTransform,
+) -> Option<Vec2> {
+    let ray = camera.viewport_to_world(camera_transform, pos)?;
+    let normal = Direction3d::new(transform.affine().matrix3.z_axis.into()).ok()?;
+    let distance = ray.intersect_plane(transform.translation(), Plane3d { normal })?;
+    let local = transform
+        .affine()
+        .inverse()
+        .transform_point3(ray.get_point(distance));
+    Some(Vec2::new(local.x, -local.y))
+}
+
+pub(crate) fn update_world_joystick_area(
+    mut joysticks: Query<(&VirtualJoystickWorldArea, &mut VirtualJoystickData)>,
+) {
+    for (area, mut data) in &mut joysticks {
+        data.interactable_zone_rect = Rect::from_center_size(Vec2::ZERO, area.size);
+        data.radius = Vec2::splat(area.knob_radius.unwrap_or(area.size.min_element() / 2.));
+    }
+}
+
+#[allow(clippy::type_complexity)]
+pub(crate) fn update_world_joystick_transforms<S: VirtualJoystickID>(
+    joysticks: Query<
+        (&VirtualJoystickNode<S>, &VirtualJoystickData, &Children),
+        With<VirtualJoystickWorldArea>,
+    >,
+    mut knobs: Query<
+        &mut Transform,
+        (
+            With<VirtualJoystickWorldKnob>,
+            Without<VirtualJoystickWorldBackground>,
+        ),
+    >,
+    mut backgrounds: Query<
+        &mut Transform,
+        (
+            With<VirtualJoystickWorldBackground>,
+            Without<VirtualJoystickWorldKnob>,
+        ),
+    >,
+) {
+    for (node, data, children) in &joysticks {
+        let base = match node.behaviour {
+            VirtualJoystickType::Fixed => Vec2::ZERO,
+            VirtualJoystickType::Floating | VirtualJoystickType::Dynamic => {
+                if data.id_drag.is_none() {
+                    Vec2::ZERO
+                } else {
+                    Vec2::new(data.start_pos.x, -data.start_pos.y)
+                }
+            }
+        };
+        let offset = node
+            .axis
+            .handle_xy(-data.delta.x * data.radius.x, data.delta.y * data.radius.y);
+        for child in children {
+            if let Ok(mut transform) = knobs.get_mut(*child) {
+                transform.translation = (base + offset).extend(transform.translation.z);
+            } else if let Ok(mut transform) = backgrounds.get_mut(*child) {
+                transform.translation = base.extend(transform.translation.z);
+            }
+        }
+    }
+}
@@ -0,0 +1,29 @@
+#![allow(dead_code)]
+
+use bevy::prelude::*;
+use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+
+/// Fixed joystick "Move" on a 100x100 area at the origin, knob radius 50
+pub fn fixed_joystick(driver: &mut VirtualJoystickTestDriver<String>) -> Entity {
+    joystick(driver, "Move", Rect::new(0., 0., 100., 100.))
+}
+
+pub fn joystick(driver: &mut VirtualJoystickTestDriver<String>, id: &str, zone: Rect) -> Entity {
+    driver.spawn_joystick(
+        VirtualJoystickNode {
+            id: id.to_string(),
+            behaviour: VirtualJoystickType::Fixed,
+            ..default()
+        },
+        zone,
+    )
+}
+
+/// Types of events, ignoring drags
+pub fn types(events: &[VirtualJoystickEvent<String>]) -> Vec<VirtualJoystickEventType> {
+    events
+        .iter()
+        .map(VirtualJoystickEvent::get_type)
+        .filter(|event| *event != VirtualJoystickEventType::Drag)
+        .collect()
+}
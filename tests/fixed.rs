@@ -0,0 +1,69 @@
+mod common;
+
+use bevy::prelude::*;
+use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+
+use common::fixed_joystick;
+
+/// Presses and releases seen by each fixed tick
+#[derive(Resource, Default)]
+struct Ticks(Vec<(u32, u32)>);
+
+fn read_fixed_input(fixed: Query<&VirtualJoystickFixedInput>, mut ticks: ResMut<Ticks>) {
+    for fixed in &fixed {
+        ticks.0.push((fixed.presses, fixed.releases));
+    }
+}
+
+#[test]
+fn same_frame_press_and_release_reach_fixed_update() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+    // One fixed tick every three frames
+    driver
+        .app
+        .insert_resource(Time::<Fixed>::from_seconds(0.05))
+        .init_resource::<Ticks>()
+        .add_systems(FixedUpdate, read_fixed_input);
+    driver
+        .app
+        .world
+        .entity_mut(joystick)
+        .insert(VirtualJoystickFixedInput::default());
+    // Time does not advance on the first frame
+    driver.update();
+
+    let pos = Vec2::new(50., 50.);
+    driver.app.world.send_event(InputEvent::StartDrag {
+        id: 0,
+        pos,
+        pointer: VirtualJoystickPointer::Touch,
+        window: None,
+    });
+    driver.release(0, pos);
+    let fixed = driver
+        .app
+        .world
+        .get::<VirtualJoystickFixedInput>(joystick)
+        .unwrap();
+    assert!(fixed.just_pressed() && fixed.just_released());
+    assert!(!fixed.pressed);
+
+    driver.update();
+    assert!(driver.app.world.resource::<Ticks>().0.is_empty());
+    let fixed = driver
+        .app
+        .world
+        .get::<VirtualJoystickFixedInput>(joystick)
+        .unwrap();
+    assert_eq!((fixed.presses, fixed.releases), (1, 1));
+
+    driver.update();
+    assert_eq!(driver.app.world.resource::<Ticks>().0, [(1, 1)]);
+    let fixed = driver
+        .app
+        .world
+        .get::<VirtualJoystickFixedInput>(joystick)
+        .unwrap();
+    assert_eq!((fixed.presses, fixed.releases), (0, 0));
+}
@@ -0,0 +1,128 @@
+mod common;
+
+use bevy::prelude::*;
+use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+
+use common::{fixed_joystick, types};
+
+fn driver_with_gestures() -> VirtualJoystickTestDriver<String> {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+    driver
+        .app
+        .world
+        .entity_mut(joystick)
+        .insert(VirtualJoystickGestures::default());
+    driver
+}
+
+#[test]
+fn tap_and_double_tap() {
+    let mut driver = driver_with_gestures();
+    let pos = Vec2::new(50., 50.);
+
+    driver.press(0, pos).release(0, pos);
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up,
+            VirtualJoystickEventType::Tap
+        ]
+    );
+
+    driver.press(0, pos).release(0, pos);
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up,
+            VirtualJoystickEventType::Tap,
+            VirtualJoystickEventType::DoubleTap
+        ]
+    );
+}
+
+#[test]
+fn release_far_from_press_is_not_a_tap() {
+    let mut driver = driver_with_gestures();
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(55., 50.))
+        .release(0, Vec2::new(99., 50.));
+    let events = types(&driver.take_events());
+    assert!(!events.contains(&VirtualJoystickEventType::Tap));
+    assert!(!events.contains(&VirtualJoystickEventType::DoubleTap));
+}
+
+#[test]
+fn long_press() {
+    let mut driver = driver_with_gestures();
+
+    driver.press(0, Vec2::new(50., 50.));
+    for _ in 0..40 {
+        driver.update();
+    }
+    driver.release(0, Vec2::new(50., 50.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::LongPress,
+            VirtualJoystickEventType::Up
+        ]
+    );
+}
+
+#[test]
+fn flick_without_tap() {
+    let mut driver = driver_with_gestures();
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(50., 30.))
+        .release(0, Vec2::new(50., 10.));
+    let events = driver.take_events();
+    assert_eq!(
+        types(&events),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up,
+            VirtualJoystickEventType::Flick
+        ]
+    );
+    let flick = events.last().unwrap().flick().unwrap();
+    // Pointer moved up, y up is positive
+    assert_eq!(flick.direction, Vec2::Y);
+    assert!(flick.speed >= VirtualJoystickGestures::default().flick_min_speed);
+}
+
+#[test]
+fn short_flick_is_not_a_tap() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+    let mut gestures = VirtualJoystickGestures::default();
+    gestures.tap_max_distance = 20.;
+    driver.app.world.entity_mut(joystick).insert(gestures);
+
+    // Fast enough for a flick while within tap distance
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .release(0, Vec2::new(65., 50.));
+    // Next tap is not a double tap of the flick
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .release(0, Vec2::new(50., 50.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up,
+            VirtualJoystickEventType::Flick,
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up,
+            VirtualJoystickEventType::Tap
+        ]
+    );
+}
@@ -0,0 +1,177 @@
+mod common;
+
+use bevy::{input::touch::TouchPhase, prelude::*};
+use virtual_joystick::{
+    testing::{VirtualJoystickTestDriver, FRAME_TIME},
+    *,
+};
+
+use common::{fixed_joystick, types};
+
+#[test]
+fn press_drag_release() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(75., 50.));
+    let data = driver.data(joystick);
+    assert_eq!(data.id_drag, Some(0));
+    assert_eq!(data.start_pos, Vec2::new(50., 50.));
+    assert_eq!(data.current_pos, Vec2::new(75., 50.));
+
+    let events = driver.take_events();
+    assert_eq!(events[0].get_type(), VirtualJoystickEventType::Press);
+    let drag = events.last().unwrap();
+    assert_eq!(drag.get_type(), VirtualJoystickEventType::Drag);
+    assert_eq!(drag.id(), "Move");
+    // Right and up are positive
+    assert_eq!(*drag.axis(), Vec2::new(0.5, 0.));
+
+    driver.release(0, Vec2::new(75., 50.));
+    assert_eq!(types(&driver.take_events()), [VirtualJoystickEventType::Up]);
+    let data = driver.data(joystick);
+    assert!(data.id_drag.is_none());
+    assert_eq!(data.delta, Vec2::ZERO);
+}
+
+#[test]
+fn press_outside_is_ignored() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+
+    driver
+        .press(0, Vec2::new(150., 50.))
+        .move_to(0, Vec2::new(50., 50.));
+    assert!(driver.take_events().is_empty());
+    assert!(driver.data(joystick).id_drag.is_none());
+}
+
+#[test]
+fn cancel_resets_joystick() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(80., 50.))
+        .cancel(0, Vec2::new(80., 50.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Cancel
+        ]
+    );
+    assert!(driver.data(joystick).id_drag.is_none());
+    assert_eq!(driver.data(joystick).delta, Vec2::ZERO);
+}
+
+#[test]
+fn touch_press_and_release_in_same_frame() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+    driver.app.add_event::<TouchInput>();
+    let window = driver.app.world.spawn_empty().id();
+
+    for phase in [TouchPhase::Started, TouchPhase::Ended] {
+        driver.app.world.send_event(TouchInput {
+            phase,
+            position: Vec2::new(50., 50.),
+            window,
+            force: None,
+            id: 0,
+        });
+    }
+    driver.update();
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up
+        ]
+    );
+    assert!(driver.data(joystick).id_drag.is_none());
+}
+
+#[test]
+fn disable_while_dragging() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(80., 50.));
+    driver.take_events();
+
+    driver
+        .app
+        .world
+        .entity_mut(joystick)
+        .insert(VirtualJoystickDisabled);
+    driver.update();
+    assert_eq!(types(&driver.take_events()), [VirtualJoystickEventType::Up]);
+    assert!(driver.data(joystick).id_drag.is_none());
+
+    // Pointers are ignored until enabled again
+    driver
+        .move_to(0, Vec2::new(90., 50.))
+        .press(1, Vec2::new(50., 50.));
+    assert!(driver.take_events().is_empty());
+
+    driver
+        .app
+        .world
+        .entity_mut(joystick)
+        .remove::<VirtualJoystickDisabled>();
+    driver.press(2, Vec2::new(50., 50.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [VirtualJoystickEventType::Press]
+    );
+}
+
+#[test]
+fn drag_derivatives() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    fixed_joystick(&mut driver);
+    // Time does not advance on the first frame
+    driver.update();
+    let dt = FRAME_TIME.as_secs_f32();
+
+    driver.press(0, Vec2::new(50., 50.)).take_events();
+    // Axis 0.2 then 0.6, the knob radius is 50
+    let expected = [
+        (
+            60.,
+            Vec2::new(0.2 / dt, 0.),
+            Vec2::new(0.2 / dt / dt, 0.),
+            dt,
+        ),
+        (
+            80.,
+            Vec2::new(0.4 / dt, 0.),
+            Vec2::new(0.2 / dt / dt, 0.),
+            2. * dt,
+        ),
+        (80., Vec2::ZERO, Vec2::new(-0.4 / dt / dt, 0.), 3. * dt),
+    ];
+    for (x, velocity, acceleration, press_duration) in expected {
+        driver.move_to(0, Vec2::new(x, 50.));
+        let events = driver.take_events();
+        let drag = events.last().unwrap();
+        assert_eq!(drag.get_type(), VirtualJoystickEventType::Drag);
+        assert!(
+            drag.velocity().abs_diff_eq(velocity, 1e-3),
+            "{:?}",
+            drag.velocity()
+        );
+        assert!(
+            drag.acceleration().abs_diff_eq(acceleration, 1e-1),
+            "{:?}",
+            drag.acceleration()
+        );
+        assert!((drag.press_duration() - press_duration).abs() < 1e-6);
+    }
+}
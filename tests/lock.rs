@@ -0,0 +1,112 @@
+mod common;
+
+use bevy::prelude::*;
+use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+
+use common::{fixed_joystick, types};
+
+fn locking_joystick(
+    driver: &mut VirtualJoystickTestDriver<String>,
+    lock: VirtualJoystickLock,
+) -> Entity {
+    let joystick = fixed_joystick(driver);
+    driver.app.world.entity_mut(joystick).insert(lock);
+    joystick
+}
+
+#[test]
+fn lock_past_ring_and_unlock_on_touch() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = locking_joystick(&mut driver, VirtualJoystickLock::default());
+
+    // Pointer at 1.4 times the knob radius above the base
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(50., -20.))
+        .release(0, Vec2::new(50., -20.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Locked,
+            VirtualJoystickEventType::Up
+        ]
+    );
+    assert!(driver.data(joystick).locked);
+
+    // Keeps moving up after release
+    driver.update();
+    let events = driver.take_events();
+    assert_eq!(events.len(), 1);
+    assert_eq!(events[0].get_type(), VirtualJoystickEventType::Drag);
+    assert_eq!(*events[0].axis(), Vec2::Y);
+
+    driver.press(1, Vec2::new(50., 50.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Unlocked,
+            VirtualJoystickEventType::Press
+        ]
+    );
+    assert!(!driver.data(joystick).locked);
+}
+
+#[test]
+fn unlock_when_moving_back() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = locking_joystick(&mut driver, VirtualJoystickLock::default());
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(50., -20.))
+        .move_to(0, Vec2::new(50., 10.))
+        .release(0, Vec2::new(50., 10.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Locked,
+            VirtualJoystickEventType::Unlocked,
+            VirtualJoystickEventType::Up
+        ]
+    );
+    assert!(!driver.data(joystick).locked);
+    assert_eq!(driver.data(joystick).delta, Vec2::ZERO);
+}
+
+#[test]
+fn lock_icon() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = locking_joystick(
+        &mut driver,
+        VirtualJoystickLock {
+            distance: None,
+            icon: Some(Rect::from_center_size(Vec2::new(0., 1.6), Vec2::splat(0.6))),
+        },
+    );
+
+    // Beyond the ring but beside the icon
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(120., 50.));
+    assert!(!driver.data(joystick).locked);
+
+    driver
+        .move_to(0, Vec2::new(50., -30.))
+        .release(0, Vec2::new(50., -30.));
+    assert!(driver.data(joystick).locked);
+
+    driver
+        .app
+        .world
+        .entity_mut(joystick)
+        .insert(VirtualJoystickDisabled);
+    driver.take_events();
+    driver.update();
+    assert_eq!(
+        types(&driver.take_events()),
+        [VirtualJoystickEventType::Unlocked]
+    );
+    assert!(!driver.data(joystick).locked);
+}
@@ -0,0 +1,159 @@
+mod common;
+
+use bevy::{prelude::*, reflect::GetField, ui::UiStack};
+use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+
+use common::{fixed_joystick, joystick, types};
+
+/// Joysticks "Left" and "Right" overlapping on x from 50 to 100,
+/// "Right" has the higher priority
+fn overlapping(
+    driver: &mut VirtualJoystickTestDriver<String>,
+    allow_stealing: bool,
+) -> (Entity, Entity) {
+    let left = joystick(driver, "Left", Rect::new(0., 0., 100., 100.));
+    let right = joystick(driver, "Right", Rect::new(50., 0., 150., 100.));
+    driver
+        .app
+        .world
+        .entity_mut(right)
+        .insert(VirtualJoystickPriority {
+            priority: 1,
+            allow_stealing,
+            ..default()
+        });
+    (left, right)
+}
+
+fn captured(driver: &VirtualJoystickTestDriver<String>, id: u64) -> Option<Entity> {
+    driver
+        .app
+        .world
+        .resource::<VirtualJoystickCapturedPointers>()
+        .owner(id, VirtualJoystickPointer::Touch)
+}
+
+#[test]
+fn overlap_without_stealing() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let (left, right) = overlapping(&mut driver, false);
+
+    driver.press(0, Vec2::new(75., 50.));
+    assert_eq!(driver.data(right).id_drag, Some(0));
+    assert!(driver.data(left).id_drag.is_none());
+    assert_eq!(captured(&driver, 0), Some(right));
+
+    // Busy joystick keeps its pointer, the second one goes to the other joystick
+    driver.press(1, Vec2::new(80., 50.));
+    assert_eq!(driver.data(right).id_drag, Some(0));
+    assert_eq!(driver.data(left).id_drag, Some(1));
+
+    // A pointer drives only its owner, even over another joystick
+    driver.move_to(0, Vec2::new(10., 50.));
+    assert_eq!(driver.data(left).current_pos, Vec2::new(80., 50.));
+    assert_eq!(driver.data(right).current_pos, Vec2::new(10., 50.));
+}
+
+#[test]
+fn overlap_with_stealing() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let (left, right) = overlapping(&mut driver, true);
+
+    driver
+        .press(0, Vec2::new(75., 50.))
+        .press(1, Vec2::new(80., 50.));
+    assert_eq!(driver.data(right).id_drag, Some(1));
+    assert!(driver.data(left).id_drag.is_none());
+    assert_eq!(captured(&driver, 1), Some(right));
+    assert!(captured(&driver, 0).is_none());
+
+    let events = driver.take_events();
+    assert!(events
+        .iter()
+        .all(|event| event.id() == "Right" && event.get_type() != VirtualJoystickEventType::Up));
+}
+
+#[test]
+fn handoff_to_second_finger() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+    driver
+        .app
+        .world
+        .entity_mut(joystick)
+        .insert(VirtualJoystickPriority {
+            allow_handoff: true,
+            ..default()
+        });
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .press(1, Vec2::new(20., 50.))
+        .move_to(1, Vec2::new(10., 50.))
+        .release(0, Vec2::new(50., 50.));
+    let data = driver.data(joystick);
+    assert_eq!(data.id_drag, Some(1));
+    assert_eq!(data.current_pos, Vec2::new(10., 50.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [VirtualJoystickEventType::Press]
+    );
+
+    driver.release(1, Vec2::new(10., 50.));
+    assert_eq!(types(&driver.take_events()), [VirtualJoystickEventType::Up]);
+    assert!(driver.data(joystick).id_drag.is_none());
+}
+
+#[test]
+fn release_without_handoff() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .press(1, Vec2::new(20., 50.))
+        .release(0, Vec2::new(50., 50.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up
+        ]
+    );
+    assert!(driver.data(joystick).id_drag.is_none());
+}
+
+#[test]
+fn ui_button_blocks_joystick() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = fixed_joystick(&mut driver);
+    // Layout is not computed headless, size the button by hand
+    let mut node = Node::default();
+    *node.get_field_mut::<Vec2>("calculated_size").unwrap() = Vec2::splat(40.);
+    let mut visibility = ViewVisibility::default();
+    visibility.set();
+    let button = driver
+        .app
+        .world
+        .spawn((
+            node,
+            GlobalTransform::from_translation(Vec3::new(50., 50., 0.)),
+            visibility,
+            Interaction::default(),
+        ))
+        .id();
+    driver.app.insert_resource(UiStack {
+        uinodes: vec![joystick, button],
+    });
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .release(0, Vec2::new(50., 50.));
+    assert!(driver.take_events().is_empty());
+
+    driver.press(1, Vec2::new(90., 90.));
+    assert_eq!(
+        types(&driver.take_events()),
+        [VirtualJoystickEventType::Press]
+    );
+}
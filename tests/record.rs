@@ -0,0 +1,95 @@
+mod common;
+
+use std::time::Duration;
+
+use bevy::{prelude::*, time::TimeUpdateStrategy};
+use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+
+use common::{fixed_joystick, types};
+
+fn driver_with_record() -> VirtualJoystickTestDriver<String> {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    driver
+        .app
+        .add_plugins(VirtualJoystickRecordPlugin::<String>::default());
+    fixed_joystick(&mut driver);
+    driver
+}
+
+/// Fields compared between recorded and replayed events
+fn summary(
+    events: &[VirtualJoystickEvent<String>],
+) -> Vec<(VirtualJoystickEventType, Vec2, Vec2, f32)> {
+    events
+        .iter()
+        .map(|event| {
+            (
+                event.get_type(),
+                *event.axis(),
+                *event.velocity(),
+                event.press_duration(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn replay_matches_recording() {
+    let mut driver = driver_with_record();
+    driver
+        .app
+        .world
+        .resource_mut::<VirtualJoystickRecorder<String>>()
+        .start();
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_along(0, [Vec2::new(60., 50.), Vec2::new(80., 40.)])
+        .release(0, Vec2::new(80., 40.));
+    let recording = driver
+        .app
+        .world
+        .resource_mut::<VirtualJoystickRecorder<String>>()
+        .stop()
+        .unwrap();
+    let recorded = recording
+        .frames
+        .iter()
+        .flat_map(|frame| frame.output.iter().cloned())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        types(&recorded),
+        [
+            VirtualJoystickEventType::Press,
+            VirtualJoystickEventType::Up
+        ]
+    );
+    assert_eq!(summary(&recorded), summary(&driver.take_events()));
+
+    // Replayed at another frame rate, time follows the recording
+    let mut replayed = driver_with_record();
+    replayed
+        .app
+        .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+            50,
+        )))
+        .insert_resource(VirtualJoystickReplay::new(&recording));
+    while !replayed
+        .app
+        .world
+        .resource::<VirtualJoystickReplay>()
+        .is_finished()
+    {
+        replayed.update();
+    }
+    assert_eq!(summary(&replayed.take_events()), summary(&recorded));
+}
+
+#[test]
+fn record_plugin_adds_core_plugin() {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(VirtualJoystickRecordPlugin::<String>::default())
+        .add_plugins(VirtualJoystickPlugin::<String>::default());
+    app.update();
+    assert!(app.is_plugin_added::<VirtualJoystickCorePlugin>());
+}
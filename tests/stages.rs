@@ -0,0 +1,67 @@
+mod common;
+
+use bevy::prelude::*;
+use virtual_joystick::{testing::VirtualJoystickTestDriver, *};
+
+/// Walk above 0.1 and run above 0.8 with the default hysteresis of 0.05
+fn walk_run_joystick(driver: &mut VirtualJoystickTestDriver<String>) -> Entity {
+    driver.spawn_joystick(
+        VirtualJoystickNode {
+            id: "Move".to_string(),
+            behaviour: VirtualJoystickType::Fixed,
+            stages: VirtualJoystickStages::new([0.1, 0.8]),
+            ..default()
+        },
+        Rect::new(0., 0., 100., 100.),
+    )
+}
+
+/// Stages of the stage change events
+fn stage_changes(events: &[VirtualJoystickEvent<String>]) -> Vec<usize> {
+    events
+        .iter()
+        .filter(|event| event.get_type() == VirtualJoystickEventType::StageChange)
+        .map(VirtualJoystickEvent::stage)
+        .collect()
+}
+
+#[test]
+fn stages_follow_deflection() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = walk_run_joystick(&mut driver);
+
+    // Deflection 0.2 then 0.84
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(60., 50.));
+    assert_eq!(driver.data(joystick).stage, 1);
+    driver.move_to(0, Vec2::new(92., 50.));
+    assert_eq!(driver.data(joystick).stage, 2);
+
+    driver.release(0, Vec2::new(92., 50.));
+    assert_eq!(driver.data(joystick).stage, 0);
+    assert_eq!(stage_changes(&driver.take_events()), [1, 2, 0]);
+}
+
+#[test]
+fn stages_hysteresis() {
+    let mut driver = VirtualJoystickTestDriver::<String>::new();
+    let joystick = walk_run_joystick(&mut driver);
+
+    driver
+        .press(0, Vec2::new(50., 50.))
+        .move_to(0, Vec2::new(92., 50.));
+    driver.take_events();
+
+    // 0.76 is below the run threshold but within the hysteresis
+    driver.move_to(0, Vec2::new(88., 50.));
+    assert_eq!(driver.data(joystick).stage, 2);
+    assert!(stage_changes(&driver.take_events()).is_empty());
+
+    // 0.72 leaves the run stage
+    driver.move_to(0, Vec2::new(86., 50.));
+    assert_eq!(driver.data(joystick).stage, 1);
+    let events = driver.take_events();
+    assert_eq!(stage_changes(&events), [1]);
+    assert_eq!(*events[0].axis(), Vec2::new(0.72, 0.));
+}